@@ -0,0 +1,457 @@
+//! Animation module
+//!
+//! Keyframed tracks blend shape properties over time, a timeline renders
+//! one image per frame, and the frames are saved as an animated GIF
+//! or a numbered PNG sequence, for the `animate` subcommand.
+
+use crate::geometrical_shapes::{self as gs, Animatable, Drawable, Easing, Styled};
+use raster::{Color, Image};
+use std::f64::consts::PI;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::Path;
+
+/// A property changing over time, set by keyframes at times in seconds
+/// Each key carries the easing of the stretch leading up to it
+/// Before the first key and after the last the value holds still
+#[derive(Debug, Clone)]
+pub struct Track<T> {
+    keys: Vec<(f64, T, Easing)>,
+}
+
+#[allow(dead_code)]
+impl<T: Animatable> Track<T> {
+    /// Creates a track holding `value` from time 0.0
+    pub fn new(value: T) -> Self {
+        Track {
+            keys: vec![(0.0, value, Easing::Linear)],
+        }
+    }
+
+    /// Adds a keyframe reaching `value` at `time` seconds at a constant speed
+    /// Keys are kept in time order, a later key at the same time replaces the earlier one
+    pub fn key(self, time: f64, value: T) -> Self {
+        self.key_eased(time, value, Easing::Linear)
+    }
+
+    /// Adds a keyframe reaching `value` at `time` seconds along `easing`
+    pub fn key_eased(mut self, time: f64, value: T, easing: Easing) -> Self {
+        let i = self.keys.partition_point(|(key, ..)| *key <= time);
+        if i > 0 && self.keys[i - 1].0 == time {
+            self.keys[i - 1] = (time, value, easing);
+        } else {
+            self.keys.insert(i, (time, value, easing));
+        }
+        self
+    }
+
+    /// Returns the keyframes in time order
+    pub fn keys(&self) -> &[(f64, T, Easing)] {
+        &self.keys
+    }
+
+    /// Value at `time` seconds, blended between the surrounding keys
+    pub fn at(&self, time: f64) -> T {
+        let i = self.keys.partition_point(|(key, ..)| *key <= time);
+        if i == 0 {
+            return self.keys[0].1.clone();
+        }
+        if i == self.keys.len() {
+            return self.keys[i - 1].1.clone();
+        }
+
+        let ((a, from, _), (b, to, easing)) = (&self.keys[i - 1], &self.keys[i]);
+        from.interpolate(to, easing.apply((time - a) / (b - a)))
+    }
+}
+
+/// Where a frame sits in the animation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frame {
+    /// Index of the frame, starting at 0
+    pub index: usize,
+    /// Time of the frame in seconds
+    pub time: f64,
+    /// Time as a fraction of the whole animation, from 0.0 to 1.0
+    pub progress: f64,
+}
+
+/// Size, length and frame rate of an animation
+#[derive(Debug, Clone)]
+pub struct Timeline {
+    width: i32,
+    height: i32,
+    duration: f64,
+    fps: u32,
+    background: Color,
+}
+
+#[allow(dead_code)]
+impl Timeline {
+    /// Creates a timeline of `duration` seconds at 25 frames per second,
+    /// a rate GIF delays can hold exactly, on a black background
+    pub fn new(width: i32, height: i32, duration: f64) -> Self {
+        Timeline {
+            width,
+            height,
+            duration: duration.max(0.0),
+            fps: 25,
+            background: Color::black(),
+        }
+    }
+
+    /// Sets the number of frames per second, at least 1
+    pub fn with_fps(mut self, fps: u32) -> Self {
+        self.fps = fps.max(1);
+        self
+    }
+
+    /// Sets the color every frame starts from
+    pub fn with_background(mut self, color: Color) -> Self {
+        self.background = color;
+        self
+    }
+
+    pub fn fps(&self) -> u32 {
+        self.fps
+    }
+
+    /// Number of frames, always at least one
+    pub fn frame_count(&self) -> usize {
+        ((self.duration * self.fps as f64).round() as usize).max(1)
+    }
+
+    /// Every frame of the timeline in order
+    /// The last frame falls one step before the end, so looping animations do not stutter
+    pub fn frames(&self) -> impl Iterator<Item = Frame> + '_ {
+        let count = self.frame_count();
+        (0..count).map(move |index| Frame {
+            index,
+            time: index as f64 / self.fps as f64,
+            progress: index as f64 / count as f64,
+        })
+    }
+
+    /// Renders every frame by calling `draw` on a fresh background
+    pub fn render(&self, mut draw: impl FnMut(&Frame, &mut Image)) -> Animation {
+        let frames = self
+            .frames()
+            .map(|frame| {
+                let mut image = Image::blank(self.width, self.height);
+                let background = [
+                    self.background.r,
+                    self.background.g,
+                    self.background.b,
+                    self.background.a,
+                ];
+                for pixel in image.bytes.chunks_exact_mut(4) {
+                    pixel.copy_from_slice(&background);
+                }
+                draw(&frame, &mut image);
+                image
+            })
+            .collect();
+
+        Animation {
+            frames,
+            fps: self.fps,
+        }
+    }
+}
+
+/// Reasons an animation cannot be saved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnimationError {
+    /// The file or directory could not be written
+    Io(String),
+    /// The GIF encoder rejected the frames
+    Gif(String),
+    /// GIF frames are limited to 65535 pixels on each side
+    TooLarge { width: i32, height: i32 },
+}
+
+impl fmt::Display for AnimationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AnimationError::Io(message) => write!(f, "cannot write animation: {message}"),
+            AnimationError::Gif(message) => write!(f, "cannot encode GIF: {message}"),
+            AnimationError::TooLarge { width, height } => {
+                write!(f, "{width}x{height} is too large for a GIF")
+            }
+        }
+    }
+}
+
+/// Rendered frames played back at a fixed rate
+#[derive(Debug, Clone)]
+pub struct Animation {
+    frames: Vec<Image>,
+    fps: u32,
+}
+
+#[allow(dead_code)]
+impl Animation {
+    pub fn frames(&self) -> &[Image] {
+        &self.frames
+    }
+
+    pub fn fps(&self) -> u32 {
+        self.fps
+    }
+
+    /// Delay before each frame in hundredths of a second, the unit GIF uses
+    /// Rounding is carried over so the total length never drifts
+    fn delays(&self) -> Vec<u16> {
+        let at = |i: usize| (i as f64 * 100.0 / self.fps as f64).round() as u64;
+        (0..self.frames.len())
+            .map(|i| (at(i + 1) - at(i)).min(u16::MAX as u64) as u16)
+            .collect()
+    }
+
+    /// Saves the frames as a looping animated GIF
+    /// Each frame gets its own palette of up to 256 colors
+    pub fn save_gif(&self, path: impl AsRef<Path>) -> Result<(), AnimationError> {
+        let Some(first) = self.frames.first() else {
+            return Ok(());
+        };
+        let (Ok(width), Ok(height)) = (u16::try_from(first.width), u16::try_from(first.height))
+        else {
+            return Err(AnimationError::TooLarge {
+                width: first.width,
+                height: first.height,
+            });
+        };
+
+        let gif_error = |e: gif::EncodingError| AnimationError::Gif(e.to_string());
+        let file = File::create(path).map_err(|e| AnimationError::Io(e.to_string()))?;
+        let mut encoder =
+            gif::Encoder::new(BufWriter::new(file), width, height, &[]).map_err(gif_error)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(gif_error)?;
+
+        for (image, delay) in self.frames.iter().zip(self.delays()) {
+            let mut rgba = image.bytes.clone();
+            let mut frame = gif::Frame::from_rgba_speed(width, height, &mut rgba, 10);
+            frame.delay = delay;
+            encoder.write_frame(&frame).map_err(gif_error)?;
+        }
+        Ok(())
+    }
+
+    /// Saves each frame as `<prefix>_0000.png`, `<prefix>_0001.png`, ...
+    /// inside `directory`, creating it if needed, and returns the written paths
+    pub fn save_png_sequence(
+        &self,
+        directory: impl AsRef<Path>,
+        prefix: &str,
+    ) -> Result<Vec<String>, AnimationError> {
+        let directory = directory.as_ref();
+        fs::create_dir_all(directory).map_err(|e| AnimationError::Io(e.to_string()))?;
+
+        let digits = self.frames.len().to_string().len().max(4);
+        let mut paths = Vec::new();
+        for (i, image) in self.frames.iter().enumerate() {
+            let path = directory.join(format!("{prefix}_{i:0digits$}.png"));
+            let path = path.to_string_lossy().into_owned();
+            raster::save(image, &path).map_err(|e| AnimationError::Io(format!("{e:?}")))?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+}
+
+/// Renders the demo scene: a spinning cube between two pulsing circles
+fn demo() -> Animation {
+    let timeline = Timeline::new(400, 300, 2.0);
+    let radius = Track::new(20)
+        .key_eased(1.0, 60, Easing::ElasticOut)
+        .key_eased(2.0, 20, Easing::CubicInOut);
+    let color = Track::new(Color::rgb(255, 80, 80))
+        .key(1.0, Color::rgb(80, 160, 255))
+        .key(2.0, Color::rgb(255, 80, 80));
+
+    timeline.render(|frame, image| {
+        let pulse = radius.at(frame.time);
+        for x in [80, 320] {
+            gs::Circle::from_center(&gs::Point::new(x, 150), pulse)
+                .with_stroke(color.at(frame.time))
+                .draw(image);
+        }
+        gs::Cubes::from_center(&gs::Point::new(200, 150), 50)
+            .with_rotation(frame.progress * 2.0 * PI)
+            .draw(image);
+    })
+}
+
+/// Runs the `animate` subcommand: `animate [output]`
+/// Writes the demo as a GIF when the output ends in `.gif`,
+/// otherwise as numbered PNG frames inside the output directory
+pub fn run(args: &[String]) -> Result<(), String> {
+    let output = match args {
+        [] => "animation.gif",
+        [output] => output.as_str(),
+        _ => return Err("usage: animate [output.gif | directory]".into()),
+    };
+
+    let animation = demo();
+    if output.ends_with(".gif") {
+        animation.save_gif(output).map_err(|e| e.to_string())?;
+        println!("wrote {} frames to {output}", animation.frames().len());
+    } else {
+        let paths = animation
+            .save_png_sequence(output, "frame")
+            .map_err(|e| e.to_string())?;
+        println!("wrote {} frames to {output}", paths.len());
+    }
+    Ok(())
+}
+
+// Unit tests for tracks, timelines and encoding
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("drawing-{}-{name}", std::process::id()))
+    }
+
+    // Test tracks blend between keys and hold still past the ends
+    #[test]
+    fn test_track_keys() {
+        let track = Track::new(0.0).key(2.0, 10.0).key(1.0, 20.0);
+        assert_eq!(track.keys().len(), 3);
+
+        assert_eq!(track.at(-1.0), 0.0);
+        assert_eq!(track.at(0.5), 10.0);
+        assert_eq!(track.at(1.0), 20.0);
+        assert_eq!(track.at(1.5), 15.0);
+        assert_eq!(track.at(9.0), 10.0);
+
+        // A key at an existing time replaces it
+        let track = track.key(1.0, 40.0);
+        assert_eq!((track.keys().len(), track.at(1.0)), (3, 40.0));
+        assert_eq!(Track::new(7).at(3.0), 7);
+    }
+
+    // Test each stretch follows the easing of the key it leads to
+    #[test]
+    fn test_track_easing() {
+        let track = Track::new(0.0)
+            .key_eased(1.0, 100.0, Easing::QuadIn)
+            .key_eased(2.0, 0.0, Easing::QuadOut);
+
+        assert_eq!(track.at(0.5), 25.0);
+        assert_eq!(track.at(1.5), 25.0);
+        assert_eq!(track.keys()[1].2, Easing::QuadIn);
+    }
+
+    // Test points, whole numbers and colors interpolate
+    #[test]
+    fn test_track_values() {
+        let radius = Track::new(10).key(1.0, 13);
+        assert_eq!(radius.at(0.5), 12);
+
+        let from = gs::Point::new(0, 0).with_color(Color::black());
+        let to = gs::Point::new(100, -50).with_color(Color::white());
+        let point = Track::new(from).key(2.0, to).at(0.5);
+        assert_eq!((point.x, point.y), (25, -13));
+        assert!(point.color.r > 0 && point.color.r < 255);
+
+        let color = Color::red().interpolate(&Color::blue(), 1.0);
+        assert_eq!((color.r, color.g, color.b), (0, 0, 255));
+    }
+
+    // Test frame timing covers the duration without repeating the first frame
+    #[test]
+    fn test_timeline_frames() {
+        let timeline = Timeline::new(10, 10, 2.0).with_fps(10);
+        assert_eq!(timeline.frame_count(), 20);
+
+        let frames: Vec<Frame> = timeline.frames().collect();
+        assert_eq!(frames[0].time, 0.0);
+        assert_eq!(frames[5].time, 0.5);
+        assert_eq!(frames[19].progress, 0.95);
+
+        assert_eq!(Timeline::new(10, 10, 0.0).frame_count(), 1);
+        assert_eq!(Timeline::new(10, 10, 1.0).with_fps(0).fps(), 1);
+    }
+
+    // Test each frame starts from the background and sees its own time
+    #[test]
+    fn test_timeline_render() {
+        let timeline = Timeline::new(20, 10, 1.0)
+            .with_fps(4)
+            .with_background(Color::rgb(0, 0, 80));
+        let x = Track::new(0).key(1.0, 20);
+
+        let animation = timeline.render(|frame, image| {
+            gs::Point::new(x.at(frame.time), 5).draw(image);
+        });
+        assert_eq!(animation.frames().len(), 4);
+
+        for (i, image) in animation.frames().iter().enumerate() {
+            assert_eq!(image.get_pixel(19, 0).unwrap().b, 80);
+            let pixel = image.get_pixel(5 * i as i32, 5).unwrap();
+            assert_eq!((pixel.r, pixel.g, pixel.b), (255, 255, 255));
+        }
+    }
+
+    // Test GIFs loop, keep every frame and hold the frame rate
+    #[test]
+    fn test_animation_gif() {
+        let timeline = Timeline::new(16, 8, 1.0).with_fps(30);
+        let animation = timeline.render(|frame, image| {
+            let x = (frame.progress * 16.0) as i32;
+            gs::Point::new(x, 4).draw(image);
+        });
+        assert_eq!(
+            animation.delays().iter().map(|&d| d as u32).sum::<u32>(),
+            100
+        );
+
+        let path = scratch("test.gif");
+        animation.save_gif(&path).unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(File::open(&path).unwrap()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (16, 8));
+
+        let mut count = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert!((3..=4).contains(&frame.delay));
+            count += 1;
+        }
+        assert_eq!(count, 30);
+        fs::remove_file(&path).unwrap();
+
+        let huge = Animation {
+            frames: vec![Image::blank(70000, 1)],
+            fps: 25,
+        };
+        assert!(matches!(
+            huge.save_gif(scratch("huge.gif")),
+            Err(AnimationError::TooLarge { .. })
+        ));
+    }
+
+    // Test PNG sequences are numbered in order and read back unchanged
+    #[test]
+    fn test_animation_png_sequence() {
+        let animation = Timeline::new(6, 6, 1.0)
+            .with_fps(3)
+            .render(|frame, image| gs::Point::new(frame.index as i32 * 2, 2).draw(image));
+
+        let directory = scratch("frames");
+        let paths = animation.save_png_sequence(&directory, "spin").unwrap();
+        assert_eq!(paths.len(), 3);
+        assert!(paths[2].ends_with("spin_0002.png"));
+
+        for (path, image) in paths.iter().zip(animation.frames()) {
+            assert_eq!(raster::open(path).unwrap().bytes, image.bytes);
+        }
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,222 @@
+//! Image comparison module
+//!
+//! Measures how far two renderings drift apart and highlights
+//! the differing pixels, for regression tests and the `diff` subcommand.
+
+use raster::{Color, Image};
+use std::fmt;
+
+/// Statistics gathered while comparing two equally sized images
+/// Channels are ordered red, green, blue, alpha
+#[derive(Debug, Clone)]
+pub struct ImageDiff {
+    /// Largest absolute difference seen in each channel
+    pub max: [u8; 4],
+    /// Mean absolute difference of each channel over all pixels
+    pub mean: [f64; 4],
+    /// Peak signal-to-noise ratio of the color channels in dB,
+    /// infinite when the images are identical
+    pub psnr: f64,
+    /// Number of pixels with any channel differing by more than the threshold
+    pub differing: usize,
+    /// Differing pixels in red over a dimmed copy of the expected image
+    pub image: Image,
+}
+
+/// Reasons two images cannot be compared
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffError {
+    /// The images have different dimensions
+    SizeMismatch {
+        expected: (i32, i32),
+        actual: (i32, i32),
+    },
+}
+
+impl fmt::Display for DiffError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DiffError::SizeMismatch { expected, actual } => write!(
+                f,
+                "images differ in size: {}x{} vs {}x{}",
+                expected.0, expected.1, actual.0, actual.1
+            ),
+        }
+    }
+}
+
+fn channels(color: &Color) -> [u8; 4] {
+    [color.r, color.g, color.b, color.a]
+}
+
+/// Compares `actual` against `expected` pixel by pixel
+/// A pixel counts as differing when any channel is off by more than `threshold`
+pub fn compare(expected: &Image, actual: &Image, threshold: u8) -> Result<ImageDiff, DiffError> {
+    if (expected.width, expected.height) != (actual.width, actual.height) {
+        return Err(DiffError::SizeMismatch {
+            expected: (expected.width, expected.height),
+            actual: (actual.width, actual.height),
+        });
+    }
+
+    let mut image = Image::blank(expected.width, expected.height);
+    let mut max = [0u8; 4];
+    let mut sum = [0u64; 4];
+    let mut squared = 0u64;
+    let mut differing = 0;
+
+    for y in 0..expected.height {
+        for x in 0..expected.width {
+            let e = channels(&expected.get_pixel(x, y).unwrap());
+            let a = channels(&actual.get_pixel(x, y).unwrap());
+
+            let mut worst = 0;
+            for c in 0..4 {
+                let delta = e[c].abs_diff(a[c]);
+                max[c] = max[c].max(delta);
+                sum[c] += delta as u64;
+                if c < 3 {
+                    squared += (delta as u64).pow(2);
+                }
+                worst = worst.max(delta);
+            }
+
+            let marked = if worst > threshold {
+                differing += 1;
+                Color::red()
+            } else {
+                let gray = ((e[0] as u32 + e[1] as u32 + e[2] as u32) / 12) as u8;
+                Color::rgb(gray, gray, gray)
+            };
+            image.set_pixel(x, y, marked).unwrap();
+        }
+    }
+
+    let pixels = (expected.width as f64 * expected.height as f64).max(1.0);
+    let mse = squared as f64 / (pixels * 3.0);
+    let psnr = if mse == 0.0 {
+        f64::INFINITY
+    } else {
+        10.0 * (255.0 * 255.0 / mse).log10()
+    };
+
+    Ok(ImageDiff {
+        max,
+        mean: sum.map(|s| s as f64 / pixels),
+        psnr,
+        differing,
+        image,
+    })
+}
+
+impl fmt::Display for ImageDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "channel    max    mean")?;
+        for (c, name) in ["red", "green", "blue", "alpha"].iter().enumerate() {
+            writeln!(f, "{name:<7} {:>6} {:>7.3}", self.max[c], self.mean[c])?;
+        }
+        writeln!(f, "psnr: {:.2} dB", self.psnr)?;
+        write!(f, "differing pixels: {}", self.differing)
+    }
+}
+
+/// Runs the `diff` subcommand: `diff <expected> <actual> [diff.png] [--threshold N]`
+/// Prints the statistics, writes the highlighted diff when a path is given,
+/// and returns whether the images match within the threshold
+pub fn run(args: &[String]) -> Result<bool, String> {
+    let mut paths = Vec::new();
+    let mut threshold = 0;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--threshold" {
+            let value = args.next().ok_or("--threshold needs a value")?;
+            threshold = value
+                .parse()
+                .map_err(|_| format!("invalid threshold `{value}`, expected 0-255"))?;
+        } else {
+            paths.push(arg.as_str());
+        }
+    }
+
+    let (expected, actual, output) = match paths[..] {
+        [expected, actual] => (expected, actual, None),
+        [expected, actual, output] => (expected, actual, Some(output)),
+        _ => return Err("usage: diff <expected> <actual> [diff.png] [--threshold N]".into()),
+    };
+
+    let open = |path: &str| raster::open(path).map_err(|e| format!("cannot open {path}: {e:?}"));
+    let diff = compare(&open(expected)?, &open(actual)?, threshold).map_err(|e| e.to_string())?;
+
+    println!("{diff}");
+    if let Some(output) = output {
+        raster::save(&diff.image, output).map_err(|e| format!("cannot save {output}: {e:?}"))?;
+    }
+
+    Ok(diff.differing == 0)
+}
+
+// Unit tests for image comparison
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test identical images report no error and infinite PSNR
+    #[test]
+    fn test_compare_identical() {
+        let image = Image::blank(8, 8);
+        let diff = compare(&image, &image.clone(), 0).unwrap();
+
+        assert_eq!(diff.max, [0; 4]);
+        assert_eq!(diff.mean, [0.0; 4]);
+        assert!(diff.psnr.is_infinite());
+        assert_eq!(diff.differing, 0);
+        assert_eq!(diff.image.get_pixel(3, 3).unwrap().r, 0);
+    }
+
+    // Test per-channel statistics, PSNR and the threshold
+    #[test]
+    fn test_compare_statistics() {
+        let expected = Image::blank(2, 2);
+        let mut actual = Image::blank(2, 2);
+        actual.set_pixel(0, 0, Color::rgb(4, 0, 0)).unwrap();
+        actual.set_pixel(1, 1, Color::rgb(0, 100, 0)).unwrap();
+
+        let diff = compare(&expected, &actual, 0).unwrap();
+        assert_eq!(diff.max, [4, 100, 0, 0]);
+        assert_eq!(diff.mean, [1.0, 25.0, 0.0, 0.0]);
+        assert_eq!(diff.differing, 2);
+
+        // Mean squared error is (16 + 10000) / 12
+        let expected_psnr = 10.0 * (255.0f64 * 255.0 / (10016.0 / 12.0)).log10();
+        assert!((diff.psnr - expected_psnr).abs() < 1e-9);
+
+        let diff = compare(&expected, &actual, 10).unwrap();
+        assert_eq!(diff.differing, 1);
+        assert_eq!(diff.image.get_pixel(0, 0).unwrap().r, 0);
+        assert_eq!(diff.image.get_pixel(1, 1).unwrap().r, 255);
+    }
+
+    // Test images of different sizes cannot be compared
+    #[test]
+    fn test_compare_size_mismatch() {
+        let result = compare(&Image::blank(2, 2), &Image::blank(3, 2), 0);
+        assert_eq!(
+            result.unwrap_err(),
+            DiffError::SizeMismatch {
+                expected: (2, 2),
+                actual: (3, 2),
+            }
+        );
+    }
+
+    // Test bad command lines are rejected before touching any file
+    #[test]
+    fn test_run_usage() {
+        let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        assert!(run(&args(&["only-one.png"])).is_err());
+        assert!(run(&args(&["a.png", "b.png", "--threshold"])).is_err());
+        assert!(run(&args(&["a.png", "b.png", "--threshold", "300"])).is_err());
+    }
+}
@@ -0,0 +1,328 @@
+//! Image encoding module
+//!
+//! Writes binary PPM, PGM and PAM and uncompressed BMP files from any
+//! RGBA pixel buffer, without going through the `raster` crate.
+//! Only `Pixels::from_image` needs it, behind the `raster` feature.
+
+#[cfg(feature = "raster")]
+use raster::Image;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// File formats the encoders can write
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Binary portable pixmap (`P6`), RGB without alpha
+    Ppm,
+    /// Binary portable graymap (`P5`), luma only
+    Pgm,
+    /// Portable arbitrary map (`P7`), RGB with alpha
+    Pam,
+    /// Windows bitmap, 24-bit and uncompressed
+    Bmp,
+}
+
+#[allow(dead_code)]
+impl Format {
+    /// Picks the format from a file extension, ignoring case
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "ppm" => Some(Format::Ppm),
+            "pgm" => Some(Format::Pgm),
+            "pam" => Some(Format::Pam),
+            "bmp" => Some(Format::Bmp),
+            _ => None,
+        }
+    }
+}
+
+/// Reasons an image cannot be encoded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncodeError {
+    /// The buffer does not hold four bytes for every pixel
+    SizeMismatch { expected: usize, actual: usize },
+    /// The path has no extension of a known format
+    UnknownFormat(String),
+    /// The file could not be written
+    Io(String),
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EncodeError::SizeMismatch { expected, actual } => {
+                write!(f, "pixel buffer holds {actual} bytes, expected {expected}")
+            }
+            EncodeError::UnknownFormat(path) => {
+                write!(
+                    f,
+                    "unknown image format: {path}, expected .ppm, .pgm, .pam or .bmp"
+                )
+            }
+            EncodeError::Io(message) => write!(f, "cannot write image: {message}"),
+        }
+    }
+}
+
+impl From<io::Error> for EncodeError {
+    fn from(error: io::Error) -> Self {
+        EncodeError::Io(error.to_string())
+    }
+}
+
+/// A borrowed RGBA buffer, four bytes per pixel, rows from top to bottom
+#[derive(Debug, Clone, Copy)]
+pub struct Pixels<'a> {
+    width: u32,
+    height: u32,
+    rgba: &'a [u8],
+}
+
+#[allow(dead_code)]
+impl<'a> Pixels<'a> {
+    /// Wraps `rgba`, checking it holds exactly `width * height` pixels
+    pub fn new(width: u32, height: u32, rgba: &'a [u8]) -> Result<Self, EncodeError> {
+        let expected = width as usize * height as usize * 4;
+        if rgba.len() != expected {
+            return Err(EncodeError::SizeMismatch {
+                expected,
+                actual: rgba.len(),
+            });
+        }
+        Ok(Pixels {
+            width,
+            height,
+            rgba,
+        })
+    }
+
+    /// Borrows the pixels of a `raster` image
+    #[cfg(feature = "raster")]
+    pub fn from_image(image: &'a Image) -> Result<Self, EncodeError> {
+        Pixels::new(
+            image.width.max(0) as u32,
+            image.height.max(0) as u32,
+            &image.bytes,
+        )
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Rows of RGBA bytes from top to bottom
+    fn rows(&self) -> impl DoubleEndedIterator<Item = &'a [u8]> {
+        let stride = (self.width as usize * 4).max(1);
+        self.rgba.chunks_exact(stride)
+    }
+
+    /// Writes the pixels in `format`
+    pub fn write(&self, format: Format, out: &mut impl Write) -> io::Result<()> {
+        match format {
+            Format::Ppm => self.write_ppm(out),
+            Format::Pgm => self.write_pgm(out),
+            Format::Pam => self.write_pam(out),
+            Format::Bmp => self.write_bmp(out),
+        }
+    }
+
+    /// Writes a binary PPM, dropping alpha
+    pub fn write_ppm(&self, out: &mut impl Write) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        for row in self.rows() {
+            let rgb: Vec<u8> = row
+                .chunks_exact(4)
+                .flat_map(|p| [p[0], p[1], p[2]])
+                .collect();
+            out.write_all(&rgb)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a binary PGM of the Rec. 601 luma, dropping alpha
+    pub fn write_pgm(&self, out: &mut impl Write) -> io::Result<()> {
+        write!(out, "P5\n{} {}\n255\n", self.width, self.height)?;
+        for row in self.rows() {
+            let luma: Vec<u8> = row
+                .chunks_exact(4)
+                .map(|p| {
+                    let sum = 299 * p[0] as u32 + 587 * p[1] as u32 + 114 * p[2] as u32;
+                    ((sum + 500) / 1000) as u8
+                })
+                .collect();
+            out.write_all(&luma)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a PAM keeping all four channels
+    pub fn write_pam(&self, out: &mut impl Write) -> io::Result<()> {
+        write!(
+            out,
+            "P7\nWIDTH {}\nHEIGHT {}\nDEPTH 4\nMAXVAL 255\nTUPLTYPE RGB_ALPHA\nENDHDR\n",
+            self.width, self.height
+        )?;
+        out.write_all(self.rgba)
+    }
+
+    /// Writes a 24-bit uncompressed BMP, dropping alpha
+    /// Rows are stored bottom to top in BGR order, each padded to four bytes
+    pub fn write_bmp(&self, out: &mut impl Write) -> io::Result<()> {
+        const HEADERS: u32 = 14 + 40;
+        let stride = (self.width as u64 * 3).div_ceil(4) * 4;
+        let size = u32::try_from(stride)
+            .ok()
+            .and_then(|stride| stride.checked_mul(self.height))
+            .and_then(|data| data.checked_add(HEADERS))
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, "image too large for BMP")
+            })?;
+
+        // File header
+        out.write_all(b"BM")?;
+        out.write_all(&size.to_le_bytes())?;
+        out.write_all(&[0; 4])?;
+        out.write_all(&HEADERS.to_le_bytes())?;
+
+        // BITMAPINFOHEADER, 2835 pixels per meter is 72 DPI
+        out.write_all(&40u32.to_le_bytes())?;
+        out.write_all(&(self.width as i32).to_le_bytes())?;
+        out.write_all(&(self.height as i32).to_le_bytes())?;
+        out.write_all(&1u16.to_le_bytes())?;
+        out.write_all(&24u16.to_le_bytes())?;
+        out.write_all(&0u32.to_le_bytes())?;
+        out.write_all(&(size - HEADERS).to_le_bytes())?;
+        out.write_all(&2835i32.to_le_bytes())?;
+        out.write_all(&2835i32.to_le_bytes())?;
+        out.write_all(&[0; 8])?;
+
+        let mut line = Vec::with_capacity(stride as usize);
+        for row in self.rows().rev() {
+            line.clear();
+            line.extend(row.chunks_exact(4).flat_map(|p| [p[2], p[1], p[0]]));
+            line.resize(stride as usize, 0);
+            out.write_all(&line)?;
+        }
+        Ok(())
+    }
+
+    /// Saves to `path` in the format named by its extension
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), EncodeError> {
+        let path = path.as_ref();
+        let format = Format::from_path(path)
+            .ok_or_else(|| EncodeError::UnknownFormat(path.display().to_string()))?;
+
+        let mut out = BufWriter::new(File::create(path)?);
+        self.write(format, &mut out)?;
+        out.flush()?;
+        Ok(())
+    }
+}
+
+// Unit tests for the encoders
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 2x2 image: red, green on top, blue, half transparent white below
+    const RGBA: [u8; 16] = [
+        255, 0, 0, 255, 0, 255, 0, 255, //
+        0, 0, 255, 255, 255, 255, 255, 128,
+    ];
+
+    fn encode(format: Format) -> Vec<u8> {
+        let mut out = Vec::new();
+        Pixels::new(2, 2, &RGBA)
+            .unwrap()
+            .write(format, &mut out)
+            .unwrap();
+        out
+    }
+
+    // Test buffers of the wrong size are rejected
+    #[test]
+    fn test_pixels_size() {
+        assert_eq!(
+            Pixels::new(2, 2, &RGBA[..15]).unwrap_err(),
+            EncodeError::SizeMismatch {
+                expected: 16,
+                actual: 15,
+            }
+        );
+    }
+
+    // Test borrowing a raster image keeps its size
+    #[cfg(feature = "raster")]
+    #[test]
+    fn test_pixels_from_image() {
+        let image = Image::blank(3, 2);
+        let pixels = Pixels::from_image(&image).unwrap();
+        assert_eq!((pixels.width(), pixels.height()), (3, 2));
+    }
+
+    // Test netpbm headers and samples byte for byte
+    #[test]
+    fn test_encode_netpbm() {
+        let ppm = encode(Format::Ppm);
+        assert_eq!(&ppm[..11], b"P6\n2 2\n255\n");
+        assert_eq!(
+            &ppm[11..],
+            &[255, 0, 0, 0, 255, 0, 0, 0, 255, 255, 255, 255]
+        );
+
+        let pgm = encode(Format::Pgm);
+        assert_eq!(&pgm[..11], b"P5\n2 2\n255\n");
+        assert_eq!(&pgm[11..], &[76, 150, 29, 255]);
+
+        let pam = encode(Format::Pam);
+        let header = b"P7\nWIDTH 2\nHEIGHT 2\nDEPTH 4\nMAXVAL 255\nTUPLTYPE RGB_ALPHA\nENDHDR\n";
+        assert_eq!(&pam[..header.len()], header);
+        assert_eq!(&pam[header.len()..], &RGBA);
+    }
+
+    // Test BMP headers, bottom-up BGR rows and row padding
+    #[test]
+    fn test_encode_bmp() {
+        let bmp = encode(Format::Bmp);
+        let u32_at = |i: usize| u32::from_le_bytes(bmp[i..i + 4].try_into().unwrap());
+
+        assert_eq!(&bmp[..2], b"BM");
+        assert_eq!(u32_at(2) as usize, bmp.len());
+        assert_eq!(u32_at(10), 54);
+        assert_eq!((u32_at(18), u32_at(22)), (2, 2));
+        assert_eq!(u16::from_le_bytes([bmp[28], bmp[29]]), 24);
+
+        // Rows of 6 bytes are padded to 8, the bottom row comes first
+        assert_eq!(bmp.len(), 54 + 2 * 8);
+        assert_eq!(&bmp[54..62], &[255, 0, 0, 255, 255, 255, 0, 0]);
+        assert_eq!(&bmp[62..70], &[0, 0, 255, 0, 255, 0, 0, 0]);
+    }
+
+    // Test files are written in the format of their extension
+    #[test]
+    fn test_encode_save() {
+        assert_eq!(Format::from_path("out/a.PPM"), Some(Format::Ppm));
+        assert_eq!(Format::from_path("b.bmp"), Some(Format::Bmp));
+        assert_eq!(Format::from_path("c.png"), None);
+        assert_eq!(Format::from_path("noextension"), None);
+
+        let pixels = Pixels::new(2, 2, &RGBA).unwrap();
+        let path = std::env::temp_dir().join(format!("drawing-{}.pgm", std::process::id()));
+        pixels.save(&path).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), encode(Format::Pgm));
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(
+            pixels.save("image.png"),
+            Err(EncodeError::UnknownFormat(_))
+        ));
+    }
+}
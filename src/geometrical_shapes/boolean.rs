@@ -0,0 +1,687 @@
+use super::{Drawable, Point, Polygon, Polygonal};
+use std::ops::Range;
+
+/// Boolean operation applied between two polygonal shapes
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BooleanOp {
+    Union,
+    Intersection,
+    Difference,
+    Xor,
+}
+
+impl BooleanOp {
+    /// Decides whether a point belongs to the result from its membership in both operands
+    fn apply(self, in_a: bool, in_b: bool) -> bool {
+        match self {
+            BooleanOp::Union => in_a || in_b,
+            BooleanOp::Intersection => in_a && in_b,
+            BooleanOp::Difference => in_a && !in_b,
+            BooleanOp::Xor => in_a != in_b,
+        }
+    }
+}
+
+type Vertex = (f64, f64);
+
+/// Distance a degenerate vertex is moved off the other operand's boundary
+const PERTURBATION: f64 = 1e-6;
+
+/// Distance under which a vertex counts as lying on an edge
+const TOLERANCE: f64 = 1e-9;
+
+/// Collects every closed outline of a shape (outlines and holes) as float rings
+fn rings_of(shape: &dyn Polygonal) -> Vec<Vec<Vertex>> {
+    let mut rings = Vec::new();
+    for polygon in shape.to_polygons() {
+        for ring in
+            std::iter::once(polygon.vertices()).chain(polygon.holes().iter().map(|h| h.as_slice()))
+        {
+            let mut ring: Vec<Vertex> = ring.iter().map(|p| (p.x as f64, p.y as f64)).collect();
+            ring.dedup();
+            if ring.len() > 1 && ring[0] == ring[ring.len() - 1] {
+                ring.pop();
+            }
+            if ring.len() >= 3 {
+                rings.push(ring);
+            }
+        }
+    }
+    rings
+}
+
+/// Every edge of the rings as a segment, ring after ring
+fn edges(rings: &[Vec<Vertex>]) -> Vec<(Vertex, Vertex)> {
+    rings
+        .iter()
+        .flat_map(|ring| (0..ring.len()).map(move |i| (ring[i], ring[(i + 1) % ring.len()])))
+        .collect()
+}
+
+/// Even-odd point in region test over all rings
+fn contains(rings: &[Vec<Vertex>], (px, py): Vertex) -> bool {
+    let mut inside = false;
+    for ring in rings {
+        for i in 0..ring.len() {
+            let (x0, y0) = ring[i];
+            let (x1, y1) = ring[(i + 1) % ring.len()];
+            if (y0 > py) != (y1 > py) && px < x0 + (py - y0) * (x1 - x0) / (y1 - y0) {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+/// Cross product of `o -> a` and `o -> b`
+fn cross(o: Vertex, a: Vertex, b: Vertex) -> f64 {
+    (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0)
+}
+
+/// Checks whether a point lies on any edge of the rings, endpoints included
+fn on_boundary(rings: &[Vec<Vertex>], p: Vertex) -> bool {
+    edges(rings).into_iter().any(|(q0, q1)| {
+        let length = ((q1.0 - q0.0).powi(2) + (q1.1 - q0.1).powi(2)).sqrt();
+        let along = (p.0 - q0.0) * (q1.0 - q0.0) + (p.1 - q0.1) * (q1.1 - q0.1);
+        cross(q0, q1, p).abs() <= TOLERANCE * length
+            && along >= -TOLERANCE * length
+            && along <= length * length + TOLERANCE * length
+    })
+}
+
+/// Moves every vertex lying on the boundary of `others` a tiny step out of its own operand
+/// Afterwards the operands only ever cross at points inside both edges, which is
+/// what Greiner-Hormann needs to alternate entry and exit crossings. Growing the
+/// operand makes shared edges overlap slightly, so they merge in a union and leave
+/// only a sliver, lost when rounding to pixels, in an intersection
+fn perturb(rings: &mut [Vec<Vertex>], others: &[Vec<Vertex>]) {
+    let original = rings.to_vec();
+    // An irrational slope never runs along the axis aligned or diagonal edges of pixel shapes
+    let step = (PERTURBATION, PERTURBATION * 0.618_033_988_749_895);
+    for ring in rings.iter_mut() {
+        for vertex in ring.iter_mut() {
+            if !on_boundary(others, *vertex) {
+                continue;
+            }
+            let outward = (vertex.0 + step.0, vertex.1 + step.1);
+            *vertex = if contains(&original, outward) {
+                (vertex.0 - step.0, vertex.1 - step.1)
+            } else {
+                outward
+            };
+        }
+    }
+}
+
+/// Finds where segment `a0-a1` properly crosses `b0-b1`
+/// Returns (parameter on a, parameter on b, point), both parameters strictly inside (0, 1)
+fn crossing(a0: Vertex, a1: Vertex, b0: Vertex, b1: Vertex) -> Option<(f64, f64, Vertex)> {
+    let da = (a1.0 - a0.0, a1.1 - a0.1);
+    let db = (b1.0 - b0.0, b1.1 - b0.1);
+    let denom = da.0 * db.1 - da.1 * db.0;
+    if denom == 0.0 {
+        return None;
+    }
+
+    let t = ((b0.0 - a0.0) * db.1 - (b0.1 - a0.1) * db.0) / denom;
+    let u = ((b0.0 - a0.0) * da.1 - (b0.1 - a0.1) * da.0) / denom;
+    if t <= 0.0 || t >= 1.0 || u <= 0.0 || u >= 1.0 {
+        return None;
+    }
+    Some((t, u, (a0.0 + t * da.0, a0.1 + t * da.1)))
+}
+
+/// Vertex of an operand's doubly linked ring, either an original corner or a crossing
+struct Node {
+    point: Vertex,
+    next: usize,
+    prev: usize,
+    /// The same crossing in the other operand's list
+    neighbor: Option<usize>,
+    /// Whether going forward from this crossing follows the result's boundary
+    entry: bool,
+    visited: bool,
+}
+
+/// One operand as Greiner-Hormann linked lists, one circular list per ring
+struct Operand {
+    nodes: Vec<Node>,
+    rings: Vec<Range<usize>>,
+}
+
+impl Operand {
+    /// Links the rings with the crossings of each edge spliced in along it
+    /// `cuts` holds (edge parameter, point, crossing id) for every edge, in `edges` order
+    /// Returns the operand and the node index of every crossing id
+    fn new(
+        rings: &[Vec<Vertex>],
+        mut cuts: Vec<Vec<(f64, Vertex, usize)>>,
+        crossings: usize,
+    ) -> (Self, Vec<usize>) {
+        let mut nodes = Vec::new();
+        let mut ranges = Vec::new();
+        let mut at = vec![0; crossings];
+        let mut edge = 0;
+
+        let node = |point| Node {
+            point,
+            next: 0,
+            prev: 0,
+            neighbor: None,
+            entry: false,
+            visited: false,
+        };
+        for ring in rings {
+            let start = nodes.len();
+            for &vertex in ring {
+                nodes.push(node(vertex));
+                cuts[edge].sort_by(|x, y| x.0.total_cmp(&y.0));
+                for &(_, point, id) in &cuts[edge] {
+                    at[id] = nodes.len();
+                    nodes.push(node(point));
+                }
+                edge += 1;
+            }
+            let end = nodes.len();
+            for (i, node) in nodes.iter_mut().enumerate().skip(start) {
+                node.next = if i + 1 == end { start } else { i + 1 };
+                node.prev = if i == start { end - 1 } else { i - 1 };
+            }
+            ranges.push(start..end);
+        }
+        (
+            Operand {
+                nodes,
+                rings: ranges,
+            },
+            at,
+        )
+    }
+
+    /// Flags each crossing as entering or leaving the other operand, walking every ring
+    /// from its first corner, which the perturbation keeps off the other boundary
+    /// `flip` swaps the flags, so the traversal follows the part outside the other operand
+    fn mark(&mut self, other: &[Vec<Vertex>], flip: bool) {
+        for ring in &self.rings {
+            let mut inside = contains(other, self.nodes[ring.start].point);
+            for i in ring.clone() {
+                if self.nodes[i].neighbor.is_some() {
+                    self.nodes[i].entry = inside == flip;
+                    inside = !inside;
+                }
+            }
+        }
+    }
+
+    /// Checks whether a ring is crossed by the other operand anywhere
+    fn crossed(&self, ring: usize) -> bool {
+        self.rings[ring]
+            .clone()
+            .any(|i| self.nodes[i].neighbor.is_some())
+    }
+}
+
+/// Greiner-Hormann clipping of the rings of `a` by those of `b`
+/// Handles union, intersection and difference; the result rings are in floats
+/// and their orientation is arbitrary
+fn clip(a: &[Vec<Vertex>], b: &[Vec<Vertex>], op: BooleanOp) -> Vec<Vec<Vertex>> {
+    let (mut a, mut b) = (a.to_vec(), b.to_vec());
+    perturb(&mut a, &b);
+    perturb(&mut b, &a);
+
+    // Find every crossing, the O(E²) step
+    let (edges_a, edges_b) = (edges(&a), edges(&b));
+    let mut cuts_a = vec![Vec::new(); edges_a.len()];
+    let mut cuts_b = vec![Vec::new(); edges_b.len()];
+    let mut crossings = 0;
+    for (i, &(a0, a1)) in edges_a.iter().enumerate() {
+        for (j, &(b0, b1)) in edges_b.iter().enumerate() {
+            if let Some((t, u, point)) = crossing(a0, a1, b0, b1) {
+                cuts_a[i].push((t, point, crossings));
+                cuts_b[j].push((u, point, crossings));
+                crossings += 1;
+            }
+        }
+    }
+
+    let (mut list_a, at_a) = Operand::new(&a, cuts_a, crossings);
+    let (mut list_b, at_b) = Operand::new(&b, cuts_b, crossings);
+    for (&i, &j) in at_a.iter().zip(&at_b) {
+        list_a.nodes[i].neighbor = Some(j);
+        list_b.nodes[j].neighbor = Some(i);
+    }
+    list_a.mark(&b, matches!(op, BooleanOp::Union | BooleanOp::Difference));
+    list_b.mark(&a, op == BooleanOp::Union);
+
+    // Walk from each unvisited crossing, switching operands at every crossing met
+    let mut lists = [list_a, list_b];
+    let mut result = Vec::new();
+    for start in 0..lists[0].nodes.len() {
+        if lists[0].nodes[start].neighbor.is_none() || lists[0].nodes[start].visited {
+            continue;
+        }
+        let (mut side, mut i) = (0, start);
+        let mut ring = vec![lists[0].nodes[start].point];
+        while !lists[side].nodes[i].visited {
+            let Some(neighbor) = lists[side].nodes[i].neighbor else {
+                break;
+            };
+            lists[side].nodes[i].visited = true;
+            lists[1 - side].nodes[neighbor].visited = true;
+
+            let forward = lists[side].nodes[i].entry;
+            loop {
+                let node = &lists[side].nodes[i];
+                i = if forward { node.next } else { node.prev };
+                ring.push(lists[side].nodes[i].point);
+                if lists[side].nodes[i].neighbor.is_some() {
+                    break;
+                }
+            }
+            i = lists[side].nodes[i].neighbor.unwrap_or(i);
+            side = 1 - side;
+        }
+        result.push(ring);
+    }
+
+    // Rings crossing nothing are kept whole when the result changes across them
+    for (ring, vertices) in a.iter().enumerate() {
+        if !lists[0].crossed(ring) {
+            let in_b = contains(&b, vertices[0]);
+            if op.apply(true, in_b) != op.apply(false, in_b) {
+                result.push(vertices.clone());
+            }
+        }
+    }
+    for (ring, vertices) in b.iter().enumerate() {
+        if !lists[1].crossed(ring) {
+            let in_a = contains(&a, vertices[0]);
+            if op.apply(in_a, true) != op.apply(in_a, false) {
+                result.push(vertices.clone());
+            }
+        }
+    }
+    result
+}
+
+/// Removes collinear vertices and back-and-forth spikes from a ring
+fn drop_collinear(mut points: Vec<Point>) -> Vec<Point> {
+    let as_vertex = |p: &Point| (p.x as f64, p.y as f64);
+    let mut changed = true;
+    while changed && points.len() >= 3 {
+        changed = false;
+        for i in 0..points.len() {
+            let n = points.len();
+            let prev = as_vertex(&points[(i + n - 1) % n]);
+            let next = as_vertex(&points[(i + 1) % n]);
+            if cross(prev, as_vertex(&points[i]), next) == 0.0 {
+                points.remove(i);
+                changed = true;
+                break;
+            }
+        }
+    }
+    points
+}
+
+/// Rounds a ring to pixel coordinates, dropping repeated and collinear vertices
+/// A ring passing twice through the same pixel, as happens where the operands
+/// touched at a perturbed vertex, is split there into separate rings
+fn to_points(ring: &[Vertex]) -> Vec<Vec<Point>> {
+    let mut points: Vec<Point> = ring
+        .iter()
+        .map(|v| Point::new(v.0.round() as i32, v.1.round() as i32))
+        .collect();
+    points.dedup();
+    while points.len() > 1 && points[0] == points[points.len() - 1] {
+        points.pop();
+    }
+
+    let mut rings = Vec::new();
+    let mut pending = vec![points];
+    while let Some(points) = pending.pop() {
+        let pinch = (0..points.len())
+            .find_map(|j| (0..j).find(|&i| points[i] == points[j]).map(|i| (i, j)));
+        match pinch {
+            Some((i, j)) => {
+                let mut rest = points[..i].to_vec();
+                rest.extend_from_slice(&points[j..]);
+                pending.push(rest);
+                pending.push(points[i..j].to_vec());
+            }
+            None => rings.push(drop_collinear(points)),
+        }
+    }
+    rings
+}
+
+/// Twice the signed area of a ring, positive for counter-clockwise order
+fn area(ring: &[Point]) -> f64 {
+    (0..ring.len())
+        .map(|i| {
+            let a = &ring[i];
+            let b = &ring[(i + 1) % ring.len()];
+            a.x as f64 * b.y as f64 - b.x as f64 * a.y as f64
+        })
+        .sum()
+}
+
+/// Sorts result rings into outlines with their holes by how deeply they are nested
+/// Rings inside an even number of others are outlines, turned counter-clockwise,
+/// and the rest are holes of their innermost enclosing ring, turned clockwise
+fn nest(rings: Vec<Vec<Point>>) -> Vec<(Vec<Point>, Vec<Vec<Point>>)> {
+    let floats: Vec<Vec<Vertex>> = rings
+        .iter()
+        .map(|ring| ring.iter().map(|p| (p.x as f64, p.y as f64)).collect())
+        .collect();
+    // Rings never cross, so any corner off the other ring tells which side it is on
+    let inside = |i: usize, j: usize| {
+        let other = &floats[j..=j];
+        floats[i]
+            .iter()
+            .find(|&&v| !on_boundary(other, v))
+            .is_some_and(|&v| contains(other, v))
+    };
+    let parents: Vec<Vec<usize>> = (0..rings.len())
+        .map(|i| {
+            (0..rings.len())
+                .filter(|&j| j != i && inside(i, j))
+                .collect()
+        })
+        .collect();
+
+    let mut outlines: Vec<(usize, Vec<Point>, Vec<Vec<Point>>)> = Vec::new();
+    let mut holes = Vec::new();
+    for (i, mut ring) in rings.into_iter().enumerate() {
+        if parents[i].len().is_multiple_of(2) {
+            if area(&ring) < 0.0 {
+                ring.reverse();
+            }
+            outlines.push((i, ring, Vec::new()));
+        } else {
+            if area(&ring) > 0.0 {
+                ring.reverse();
+            }
+            holes.push((i, ring));
+        }
+    }
+    for (i, hole) in holes {
+        let parent = parents[i].iter().max_by_key(|&&j| parents[j].len());
+        if let Some(outline) = outlines.iter_mut().find(|(j, ..)| Some(j) == parent) {
+            outline.2.push(hole);
+        }
+    }
+    outlines
+        .into_iter()
+        .map(|(_, outline, holes)| (outline, holes))
+        .collect()
+}
+
+/// Computes a boolean operation between two polygonal shapes
+///
+/// Both operands are flattened to their outlines and holes, each read with the
+/// even-odd rule, and clipped with Greiner-Hormann: the crossings of every pair
+/// of edges are spliced into both operands' rings (O(E²) for E edges), flagged
+/// as entering or leaving the other operand, and the result is traced by
+/// walking from crossing to crossing. Greiner-Hormann cannot handle a vertex
+/// lying on the other operand's boundary, so such vertices are first perturbed
+/// by `PERTURBATION` away from their own operand. Intersections stay in floating
+/// point until the rings are traced. Only then are they rounded to pixels, which
+/// drops the slivers the perturbation leaves and splits rings pinched at a
+/// single pixel. The xor is the union of both differences. Outlines come out
+/// counter-clockwise and holes clockwise, and the resulting polygons take the
+/// color of the first operand. Outlines of one operand must not cross each other.
+#[allow(dead_code)]
+pub fn boolean(a: &dyn Polygonal, b: &dyn Polygonal, op: BooleanOp) -> Vec<Polygon> {
+    let rings_a = rings_of(a);
+    let rings_b = rings_of(b);
+    let color = match a.to_polygons().first() {
+        Some(polygon) => polygon.color(),
+        None => return Vec::new(),
+    };
+
+    let traced = match op {
+        BooleanOp::Xor => {
+            let mut rings = clip(&rings_a, &rings_b, BooleanOp::Difference);
+            rings.extend(clip(&rings_b, &rings_a, BooleanOp::Difference));
+            rings
+        }
+        _ => clip(&rings_a, &rings_b, op),
+    };
+
+    let rings: Vec<Vec<Point>> = traced
+        .iter()
+        .flat_map(|ring| to_points(ring))
+        .filter(|ring| ring.len() >= 3 && area(ring) != 0.0)
+        .collect();
+
+    nest(rings)
+        .into_iter()
+        .map(|(outline, holes)| Polygon::from_vertices(&outline, color.clone()).with_holes(holes))
+        .collect()
+}
+
+/// Region covered by either shape
+#[allow(dead_code)]
+pub fn union(a: &dyn Polygonal, b: &dyn Polygonal) -> Vec<Polygon> {
+    boolean(a, b, BooleanOp::Union)
+}
+
+/// Region covered by both shapes
+#[allow(dead_code)]
+pub fn intersection(a: &dyn Polygonal, b: &dyn Polygonal) -> Vec<Polygon> {
+    boolean(a, b, BooleanOp::Intersection)
+}
+
+/// Region covered by the first shape with the second one cut out
+#[allow(dead_code)]
+pub fn difference(a: &dyn Polygonal, b: &dyn Polygonal) -> Vec<Polygon> {
+    boolean(a, b, BooleanOp::Difference)
+}
+
+/// Region covered by exactly one of the shapes
+#[allow(dead_code)]
+pub fn xor(a: &dyn Polygonal, b: &dyn Polygonal) -> Vec<Polygon> {
+    boolean(a, b, BooleanOp::Xor)
+}
+
+// Unit tests for polygon boolean operations
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometrical_shapes::{Circle, FillStrategy, Fillable};
+    use raster::{Color, Image};
+
+    fn square(x: i32, y: i32, size: i32) -> Polygon {
+        let corners = [
+            Point::new(x, y),
+            Point::new(x + size, y),
+            Point::new(x + size, y + size),
+            Point::new(x, y + size),
+        ];
+        Polygon::from_vertices(&corners, Color::white())
+    }
+
+    fn total_area(polygons: &[Polygon]) -> f64 {
+        polygons
+            .iter()
+            .map(|p| (area(p.vertices()) + p.holes().iter().map(|h| area(h)).sum::<f64>()) / 2.0)
+            .sum()
+    }
+
+    // Test overlapping squares for every operation by area
+    #[test]
+    fn test_boolean_overlapping_squares() {
+        let a = square(0, 0, 10);
+        let b = square(5, 5, 10);
+
+        assert_eq!(total_area(&union(&a, &b)), 175.0);
+        assert_eq!(total_area(&intersection(&a, &b)), 25.0);
+        assert_eq!(total_area(&difference(&a, &b)), 75.0);
+        assert_eq!(total_area(&xor(&a, &b)), 150.0);
+    }
+
+    // Test cutting a shape out of the middle leaves a hole
+    #[test]
+    fn test_boolean_difference_hole() {
+        let a = square(0, 0, 20);
+        let b = square(5, 5, 10);
+        let result = difference(&a, &b);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].holes().len(), 1);
+        assert_eq!(total_area(&result), 300.0);
+
+        // The hole is cut through when filling
+        let mut image = Image::blank(20, 20);
+        result[0].fill(&mut image, FillStrategy::Scanline);
+        assert_eq!(image.get_pixel(2, 2).unwrap().r, 255);
+        assert_eq!(image.get_pixel(10, 10).unwrap().r, 0);
+    }
+
+    // Test squares sharing an edge merge into one outline
+    #[test]
+    fn test_boolean_shared_edge() {
+        let a = square(0, 0, 10);
+        let b = square(10, 0, 10);
+
+        let merged = union(&a, &b);
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].vertices().len(), 4);
+        assert_eq!(total_area(&merged), 200.0);
+        assert!(intersection(&a, &b).is_empty());
+    }
+
+    // Test squares sharing part of an edge merge around the step between them
+    #[test]
+    fn test_boolean_partially_shared_edge() {
+        let a = square(0, 0, 10);
+        let b = square(10, 5, 10);
+        for (first, second) in [(&a, &b), (&b, &a)] {
+            let merged = union(first, second);
+            assert_eq!(merged.len(), 1);
+            assert_eq!(merged[0].vertices().len(), 8);
+            assert_eq!(total_area(&merged), 200.0);
+            assert!(intersection(first, second).is_empty());
+
+            let cut = difference(first, second);
+            assert_eq!(cut.len(), 1);
+            assert_eq!(cut[0].vertices().len(), 4);
+            assert_eq!(total_area(&cut), 100.0);
+        }
+    }
+
+    // Test a corner resting on the middle of an edge
+    #[test]
+    fn test_boolean_vertex_on_edge() {
+        let a = square(0, 0, 10);
+        let corners = [Point::new(5, 10), Point::new(10, 20), Point::new(0, 20)];
+        let b = Polygon::from_vertices(&corners, Color::white());
+        for (first, second) in [(&a, &b), (&b, &a)] {
+            let result = union(first, second);
+            assert_eq!(result.len(), 2);
+            assert_eq!(total_area(&result), 150.0);
+            assert!(intersection(first, second).is_empty());
+            assert_eq!(xor(first, second).len(), 2);
+        }
+        assert_eq!(difference(&a, &b)[0].vertices().len(), 4);
+        assert_eq!(difference(&b, &a)[0].vertices().len(), 3);
+    }
+
+    // Test cutting out a corner whose edges lie along the outline
+    #[test]
+    fn test_boolean_shared_corner_cut() {
+        let a = square(0, 0, 20);
+        let b = square(10, 10, 10);
+        let result = difference(&a, &b);
+        assert_eq!(total_area(&result), 300.0);
+
+        let mut image = Image::blank(20, 20);
+        for polygon in &result {
+            polygon.fill(&mut image, FillStrategy::Scanline);
+        }
+        assert_eq!(image.get_pixel(5, 5).unwrap().r, 255);
+        assert_eq!(image.get_pixel(15, 5).unwrap().r, 255);
+        assert_eq!(image.get_pixel(15, 15).unwrap().r, 0);
+
+        assert_eq!(total_area(&intersection(&a, &b)), 100.0);
+        assert_eq!(total_area(&union(&a, &b)), 400.0);
+        assert_eq!(union(&a, &b)[0].vertices().len(), 4);
+    }
+
+    // Test squares touching at a single corner stay two separate outlines
+    #[test]
+    fn test_boolean_touching_corner() {
+        // Both diagonals, with the squares in either order
+        let pairs = [
+            (square(0, 0, 10), square(10, 10, 10)),
+            (square(10, 0, 10), square(0, 10, 10)),
+        ];
+        for (a, b) in &pairs {
+            for result in [union(a, b), union(b, a), xor(a, b), xor(b, a)] {
+                assert_eq!(result.len(), 2);
+                assert!(
+                    result
+                        .iter()
+                        .all(|p| p.vertices().len() == 4 && p.holes().is_empty())
+                );
+                assert_eq!(total_area(&result), 200.0);
+            }
+            assert!(intersection(a, b).is_empty());
+            assert_eq!(total_area(&difference(a, b)), 100.0);
+        }
+
+        // The xor of overlapping squares is two L shapes touching at two corners,
+        // not an outline with a hole pinched against it
+        let (a, b) = (square(0, 0, 10), square(5, 5, 10));
+        for result in [xor(&a, &b), xor(&b, &a)] {
+            assert_eq!(result.len(), 2);
+            assert!(
+                result
+                    .iter()
+                    .all(|p| p.vertices().len() == 6 && p.holes().is_empty())
+            );
+            assert_eq!(total_area(&result), 150.0);
+        }
+    }
+
+    // Test disjoint and nested operands
+    #[test]
+    fn test_boolean_disjoint_and_nested() {
+        let a = square(0, 0, 10);
+        let far = square(50, 50, 10);
+        assert_eq!(union(&a, &far).len(), 2);
+        assert!(intersection(&a, &far).is_empty());
+        assert_eq!(total_area(&difference(&a, &far)), 100.0);
+
+        let inner = square(2, 2, 4);
+        assert_eq!(total_area(&intersection(&a, &inner)), 16.0);
+        assert!(difference(&inner, &a).is_empty());
+    }
+
+    // Test a rectangle with a flattened circle cut out
+    #[test]
+    fn test_boolean_rectangle_minus_circle() {
+        let rect = square(0, 0, 100);
+        let circle = Circle::new(&Point::new(50, 50), 20);
+        let result = difference(&rect, &circle);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].holes().len(), 1);
+        let cut_out = area(circle.to_polygons()[0].vertices()) / 2.0;
+        assert_eq!(total_area(&result), 100.0 * 100.0 - cut_out);
+        assert_eq!(result[0].color().r, 255);
+    }
+
+    // Test empty operands
+    #[test]
+    fn test_boolean_edge_cases() {
+        let a = square(0, 0, 10);
+        let empty = Polygon::new(&[]);
+
+        assert_eq!(total_area(&union(&a, &empty)), 100.0);
+        assert!(intersection(&a, &empty).is_empty());
+        assert!(difference(&empty, &a).is_empty());
+    }
+}
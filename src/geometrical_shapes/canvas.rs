@@ -0,0 +1,223 @@
+use super::{ClipRect, Displayable};
+use raster::{Color, Image};
+
+/// An owned RGBA surface, one `[r, g, b, a]` entry per pixel, rows from top to bottom
+/// Writes go straight to memory without the per-pixel checks of `raster::Image`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Canvas {
+    width: i32,
+    height: i32,
+    pixels: Vec<[u8; 4]>,
+}
+
+/// Bytes of a color as stored in a canvas
+pub(super) fn rgba(color: &Color) -> [u8; 4] {
+    [color.r, color.g, color.b, color.a]
+}
+
+#[allow(dead_code)]
+impl Canvas {
+    /// Creates an opaque black canvas, like `Image::blank`
+    pub fn new(width: i32, height: i32) -> Self {
+        Canvas::filled(width, height, &Color::black())
+    }
+
+    /// Creates a canvas with every pixel set to `color`
+    pub fn filled(width: i32, height: i32, color: &Color) -> Self {
+        let (width, height) = (width.max(0), height.max(0));
+        Canvas {
+            width,
+            height,
+            pixels: vec![rgba(color); width as usize * height as usize],
+        }
+    }
+
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    /// Color of a pixel, `None` outside the canvas
+    pub fn get_pixel(&self, x: i32, y: i32) -> Option<Color> {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return None;
+        }
+        let [r, g, b, a] = self.pixels[(y * self.width + x) as usize];
+        Some(Color::rgba(r, g, b, a))
+    }
+
+    /// Sets every pixel to `color`
+    pub fn clear(&mut self, color: &Color) {
+        self.pixels.fill(rgba(color));
+    }
+
+    /// Sets every pixel of `area` to `color`, clipped to the canvas
+    pub fn fill_rect(&mut self, area: &ClipRect, color: &Color) {
+        let area = area.intersect(&ClipRect::from_size(self.width, self.height));
+        if area.is_empty() {
+            return;
+        }
+        let value = rgba(color);
+        for y in area.top..=area.bottom {
+            self.row_mut(y)[area.left as usize..=area.right as usize].fill(value);
+        }
+    }
+
+    /// Pixels of row `y`, which must lie on the canvas
+    pub fn row(&self, y: i32) -> &[[u8; 4]] {
+        let start = (y * self.width) as usize;
+        &self.pixels[start..start + self.width as usize]
+    }
+
+    /// Mutable pixels of row `y`, which must lie on the canvas
+    pub fn row_mut(&mut self, y: i32) -> &mut [[u8; 4]] {
+        let start = (y * self.width) as usize;
+        &mut self.pixels[start..start + self.width as usize]
+    }
+
+    /// Every pixel, rows from top to bottom, for renderers that split the canvas
+    pub(super) fn pixels_mut(&mut self) -> &mut [[u8; 4]] {
+        &mut self.pixels
+    }
+
+    /// Every pixel as RGBA bytes, in the layout `raster::Image` and the encoders expect
+    pub fn as_bytes(&self) -> &[u8] {
+        self.pixels.as_flattened()
+    }
+
+    /// Copies the pixels of an image
+    pub fn from_image(image: &Image) -> Self {
+        let mut canvas = Canvas::new(image.width, image.height);
+        for (pixel, bytes) in canvas.pixels.iter_mut().zip(image.bytes.chunks_exact(4)) {
+            pixel.copy_from_slice(bytes);
+        }
+        canvas
+    }
+
+    /// Copies the pixels into a new image
+    pub fn to_image(&self) -> Image {
+        let mut image = Image::blank(self.width, self.height);
+        image.bytes.copy_from_slice(self.as_bytes());
+        image
+    }
+}
+
+impl From<&Image> for Canvas {
+    fn from(image: &Image) -> Self {
+        Canvas::from_image(image)
+    }
+}
+
+impl From<&Canvas> for Image {
+    fn from(canvas: &Canvas) -> Self {
+        canvas.to_image()
+    }
+}
+
+impl Displayable for Canvas {
+    fn display(&mut self, x: i32, y: i32, color: Color) {
+        if x >= 0 && x < self.width && y >= 0 && y < self.height {
+            self.pixels[(y * self.width + x) as usize] = rgba(&color);
+        }
+    }
+
+    /// Clamps the run once, then fills it as a single slice
+    fn display_span(&mut self, left: i32, right: i32, y: i32, color: Color) {
+        if y < 0 || y >= self.height {
+            return;
+        }
+        let (left, right) = (left.max(0), right.min(self.width - 1));
+        if left <= right {
+            self.row_mut(y)[left as usize..=right as usize].fill(rgba(&color));
+        }
+    }
+
+    fn bounds(&self) -> Option<ClipRect> {
+        Some(ClipRect::from_size(self.width, self.height))
+    }
+}
+
+// Unit tests for the canvas surface
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometrical_shapes::{Circle, Drawable, Line, Point, Polygon, Styled};
+
+    // Test pixels are stored, read back and ignored outside the canvas
+    #[test]
+    fn test_canvas_display() {
+        let mut canvas = Canvas::new(10, 6);
+        canvas.display(3, 2, Color::rgba(1, 2, 3, 4));
+        canvas.display(-1, 2, Color::white());
+        canvas.display(10, 0, Color::white());
+
+        let pixel = canvas.get_pixel(3, 2).unwrap();
+        assert_eq!((pixel.r, pixel.g, pixel.b, pixel.a), (1, 2, 3, 4));
+        assert!(canvas.get_pixel(10, 0).is_none());
+        assert_eq!(canvas.get_pixel(0, 0).unwrap().a, 255);
+        assert!(!canvas.can_display(&ClipRect::new(20, 0, 30, 5)));
+        assert_eq!(Canvas::new(-3, 4).as_bytes().len(), 0);
+    }
+
+    // Test spans and rectangles are clipped to the canvas
+    #[test]
+    fn test_canvas_span_rect() {
+        let mut canvas = Canvas::new(8, 4);
+        canvas.display_span(-5, 2, 1, Color::red());
+        canvas.display_span(6, i32::MAX, 1, Color::blue());
+        canvas.display_span(3, 2, 1, Color::white());
+        canvas.display_span(0, 7, 9, Color::white());
+
+        let reds: Vec<u8> = canvas.row(1).iter().map(|pixel| pixel[0]).collect();
+        assert_eq!(reds, [255, 255, 255, 0, 0, 0, 0, 0]);
+        assert_eq!(canvas.row(1)[7], [0, 0, 255, 255]);
+        assert!(canvas.row(0).iter().all(|pixel| *pixel == [0, 0, 0, 255]));
+
+        canvas.fill_rect(&ClipRect::new(6, -2, 20, 1), &Color::green());
+        assert_eq!(canvas.get_pixel(7, 0).unwrap().g, 255);
+        assert_eq!(canvas.get_pixel(5, 0).unwrap().g, 0);
+        assert_eq!(canvas.get_pixel(6, 2).unwrap().g, 0);
+
+        canvas.clear(&Color::white());
+        assert!(canvas.as_bytes().iter().all(|&byte| byte == 255));
+    }
+
+    // Test shapes draw the same pixels as on a raster image
+    #[test]
+    fn test_canvas_matches_image() {
+        let circle = Circle::new(&Point::new(40, 40), 30).with_fill(Color::red());
+        let polygon = Polygon::new(&[
+            Point::new(5, 70),
+            Point::new(60, 10),
+            Point::new(75, 75),
+            Point::new(30, 50),
+        ])
+        .with_stroke(Color::blue())
+        .with_fill(Color::rgba(0, 200, 0, 128));
+        let line = Line::from_points(&Point::new(-10, 3), &Point::new(90, 77), 3, Color::white());
+        let shapes: [&dyn Drawable; 3] = [&circle, &polygon, &line];
+
+        let mut image = Image::blank(80, 80);
+        let mut canvas = Canvas::new(80, 80);
+        for shape in shapes {
+            shape.draw(&mut image);
+            shape.draw(&mut canvas);
+        }
+        assert_eq!(canvas.as_bytes(), &image.bytes[..]);
+    }
+
+    // Test conversions to and from images keep every byte
+    #[test]
+    fn test_canvas_image_round_trip() {
+        let mut image = Image::blank(5, 3);
+        image.set_pixel(4, 2, Color::rgba(9, 8, 7, 6)).unwrap();
+
+        let canvas = Canvas::from(&image);
+        assert_eq!((canvas.width(), canvas.height()), (5, 3));
+        assert_eq!(canvas.get_pixel(4, 2).unwrap().a, 6);
+        assert_eq!(Image::from(&canvas).bytes, image.bytes);
+    }
+}
@@ -1,31 +1,34 @@
-use super::{Displayable, Drawable, Point};
+use super::stroke::StrokeMask;
+use super::{
+    Animatable, ClipRect, Displayable, Drawable, Point, Polygon, Polygonal, Style, Styled,
+};
 use rand::Rng;
-use raster::{Color, Image};
+use raster::Color;
+use std::f64::consts::PI;
 
 /// Represents a circle shape with center points and radii
 /// Contains a collection of circles (for grouped rendering)
-/// and a shared color for all circles in the collection
+/// and a shared style for all circles in the collection
+#[derive(Clone)]
 pub struct Circle {
     circles: Vec<(Point, i32)>,
-    color: Color,
+    style: Style,
 }
 
 #[allow(dead_code)]
 impl Circle {
     /// Creates a new circle with specified center and radius
-    /// Generates a random color in moderate RGB range (50-200)
+    /// Kept for compatibility, same as `from_center`
     pub fn new(center: &Point, radius: i32) -> Self {
-        let mut rng = rand::thread_rng();
-        let mut circles = Vec::new();
-        let color = Color::rgb(
-            rng.gen_range(50..200),
-            rng.gen_range(50..200),
-            rng.gen_range(50..200),
-        );
-
-        circles.push((center.clone(), radius));
+        Circle::from_center(center, radius)
+    }
 
-        Circle { circles, color }
+    /// Creates a circle with specified center and radius, stroked 2px wide in white
+    pub fn from_center(center: &Point, radius: i32) -> Self {
+        Circle {
+            circles: vec![(center.clone(), radius)],
+            style: Style::new().width(2),
+        }
     }
 
     /// Generates a random circle within specified bounds
@@ -34,12 +37,7 @@ impl Circle {
     pub fn random(width: i32, height: i32) -> Self {
         let mut rng = rand::thread_rng();
         let mut circles = Vec::new();
-
-        let color = Color::rgb(
-            rng.gen_range(50..200),
-            rng.gen_range(50..200),
-            rng.gen_range(50..200),
-        );
+        let style = Style::random(50..200).width(2);
 
         if rng.gen_bool(0.7) {
             let radius = if rng.gen_bool(0.3) {
@@ -50,53 +48,211 @@ impl Circle {
             circles.push((Point::random(width, height), radius));
         }
 
-        Circle { circles, color }
+        Circle { circles, style }
+    }
+
+    /// Approximates a circle outline with a regular polygon
+    /// Uses enough vertices to keep each chord within half a pixel of the arc
+    fn flatten(center: &Point, radius: i32) -> Vec<Point> {
+        if radius <= 0 {
+            return Vec::new();
+        }
+
+        let r = radius as f64;
+        let step = 2.0 * (1.0 - 0.5 / r).max(-1.0).acos();
+        let segments = ((2.0 * PI / step).ceil() as usize).max(8);
+
+        (0..segments)
+            .map(|i| {
+                let angle = 2.0 * PI * i as f64 / segments as f64;
+                Point::new(
+                    center.x.saturating_add((r * angle.cos()).round() as i32),
+                    center.y.saturating_add((r * angle.sin()).round() as i32),
+                )
+            })
+            .collect()
+    }
+}
+
+impl Styled for Circle {
+    fn style(&self) -> &Style {
+        &self.style
+    }
+
+    fn style_mut(&mut self) -> &mut Style {
+        &mut self.style
+    }
+}
+
+impl Animatable for Circle {
+    /// Moves and resizes every circle and blends the style
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        Circle {
+            circles: self.circles.interpolate(&to.circles, t),
+            style: self.style.interpolate(&to.style, t),
+        }
+    }
+}
+
+/// Steps `y` of the octant walk whose mirrored pixels can land in `bounds`,
+/// as sorted inclusive ranges
+/// Each step draws on rows `cy ± y` and on columns `cx ± y`, so a surface of
+/// any size only ever needs a walk as long as its own height and width
+fn octant_ranges(bounds: Option<ClipRect>, cx: i64, cy: i64) -> Vec<(i64, i64)> {
+    let Some(area) = bounds else {
+        return vec![(0, i64::MAX)];
+    };
+    let (left, top) = (area.left as i64, area.top as i64);
+    let (right, bottom) = (area.right as i64, area.bottom as i64);
+
+    let mut ranges: Vec<(i64, i64)> = [
+        (top - cy, bottom - cy),
+        (cy - bottom, cy - top),
+        (left - cx, right - cx),
+        (cx - right, cx - left),
+    ]
+    .into_iter()
+    .map(|(from, to)| (from.max(0), to))
+    .filter(|(from, to)| from <= to)
+    .collect();
+    ranges.sort_unstable();
+
+    let mut merged: Vec<(i64, i64)> = Vec::new();
+    for (from, to) in ranges {
+        match merged.last_mut() {
+            Some(last) if from <= last.1.saturating_add(1) => last.1 = last.1.max(to),
+            _ => merged.push((from, to)),
+        }
     }
+    merged
+}
+
+/// Where the midpoint walk of a circle of `radius` stands at step `y`,
+/// as `x` and the error term, without walking the steps before it
+/// The walk keeps `err = (x - 1)² + (y + 1)² - (r - 1)² - 1`, settles on the
+/// largest `x` with `err < x` and never steps `x` down more than once per row
+fn octant_at(radius: i64, y: i64) -> (i64, i64) {
+    let r = radius as i128;
+    let err = |x: i128, y: i128| (x - 1) * (x - 1) + (y + 1) * (y + 1) - (r - 1) * (r - 1) - 1;
+    let settled = |y: i128| {
+        if y == 0 {
+            return r;
+        }
+        let mut x = ((r * r - y * y).max(0) as f64).sqrt() as i128;
+        while err(x + 1, y) < x + 1 {
+            x += 1;
+        }
+        while x > 0 && err(x, y) >= x {
+            x -= 1;
+        }
+        x
+    };
+
+    let y = y as i128;
+    let x = if y == 0 {
+        r
+    } else {
+        settled(y).max(settled(y - 1) - 1)
+    };
+    (x as i64, err(x, y) as i64)
 }
 
 impl Drawable for Circle {
     /// Draws the circle using midpoint circle algorithm
-    /// Renders with 2-pixel thickness for visibility
+    /// Renders with the style's width, the extra pixels placed inward
+    /// so the outline stays symmetric in all eight octants
     /// Implements the Drawable trait requirement
-    fn draw(&self, image: &mut Image) {
-        for (center, radius) in &self.circles {
-            let mut x = *radius;
-            let mut y = 0;
-            let mut err = 0;
+    fn draw(&self, image: &mut dyn Displayable) {
+        if !self.is_visible_on(image) {
+            return;
+        }
 
-            while x >= y {
-                for (dx, dy) in &[
-                    (x, y),
-                    (y, x),
-                    (-y, x),
-                    (-x, y),
-                    (-x, -y),
-                    (-y, -x),
-                    (y, -x),
-                    (x, -y),
-                ] {
-                    let px = center.x + dx;
-                    let py = center.y + dy;
+        if self.style.fill.is_some() {
+            for (center, radius) in &self.circles {
+                let outline = Circle::flatten(center, *radius);
+                self.style.paint_fill(&[&outline], image);
+            }
+        }
 
-                    image.display(px, py, self.color());
-                    image.display(px + 1, py, self.color());
-                    image.display(px, py + 1, self.color());
-                }
+        // Octants meet on the diagonals and axes, the mask merges those pixels
+        let bounds = image.bounds();
+        let mut mask = StrokeMask::new(image).styled(&self.style);
+        for (center, radius) in &self.circles {
+            if *radius < 0 {
+                continue;
+            }
+            let (cx, cy) = (center.x as i64, center.y as i64);
+            for (from, to) in octant_ranges(bounds, cx, cy) {
+                let (mut x, mut err) = octant_at(*radius as i64, from);
+                let mut y = from;
 
-                y += 1;
-                err += 1 + 2 * y;
-                if 2 * (err - x) + 1 > 0 {
-                    x -= 1;
-                    err += 1 - 2 * x;
+                while x >= y && y <= to {
+                    for (ox, oy) in (0..self.style.width as i64).map(|inset| (x - inset, y)) {
+                        for (dx, dy) in [
+                            (ox, oy),
+                            (oy, ox),
+                            (-oy, ox),
+                            (-ox, oy),
+                            (-ox, -oy),
+                            (-oy, -ox),
+                            (oy, -ox),
+                            (ox, -oy),
+                        ] {
+                            // Skip pixels past the edge of the coordinate space
+                            if let (Ok(px), Ok(py)) = (i32::try_from(cx + dx), i32::try_from(cy + dy)) {
+                                // Dashes run clockwise along the arc from the positive x axis
+                                let distance = match self.style.dash {
+                                    Some(_) => {
+                                        (dy as f64).atan2(dx as f64).rem_euclid(2.0 * PI)
+                                            * *radius as f64
+                                    }
+                                    None => 0.0,
+                                };
+                                mask.add_at(px, py, distance);
+                            }
+                        }
+                    }
+
+                    y += 1;
+                    err += 1 + 2 * y;
+                    if 2 * (err - x) + 1 > 0 {
+                        x -= 1;
+                        err += 1 - 2 * x;
+                    }
                 }
             }
         }
+        self.style.paint_stroke(mask, image);
     }
 
-    /// Returns the color of the circle
+    /// Returns the color of the circle's style
     /// Implements the Drawable trait requirement
     fn color(&self) -> Color {
-        self.color.clone()
+        self.style.color()
+    }
+
+    /// Covers every circle, the stroke only ever grows inward
+    fn bounding_box(&self) -> Option<ClipRect> {
+        self.circles
+            .iter()
+            .map(|(center, radius)| {
+                ClipRect::new(center.x, center.y, center.x, center.y)
+                    .expand(radius.saturating_abs().saturating_add(1))
+            })
+            .reduce(|a, b| a.union(&b))
+    }
+}
+
+impl Polygonal for Circle {
+    /// Flattens each circle into a polygon approximating its outline
+    fn to_polygons(&self) -> Vec<Polygon> {
+        self.circles
+            .iter()
+            .map(|(center, radius)| {
+                Polygon::from_vertices(&Circle::flatten(center, *radius), self.color())
+                    .with_style(self.style.clone())
+            })
+            .collect()
     }
 }
 
@@ -104,6 +260,52 @@ impl Drawable for Circle {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::geometrical_shapes::golden::{self, Tolerance};
+    use crate::geometrical_shapes::{Dash, Recording};
+    use proptest::prelude::*;
+    use std::collections::HashSet;
+
+    // Test jumping into the octant walk lands where walking would
+    #[test]
+    fn test_octant_at_matches_walk() {
+        for radius in (0..1500).chain([4095, 70_000]) {
+            let (mut x, mut y, mut err) = (radius, 0, 0);
+            while x >= y {
+                assert_eq!(octant_at(radius, y), (x, err), "radius {radius} step {y}");
+                y += 1;
+                err += 1 + 2 * y;
+                if 2 * (err - x) + 1 > 0 {
+                    x -= 1;
+                    err += 1 - 2 * x;
+                }
+            }
+        }
+    }
+
+    // Test huge circles crossing the surface draw the same pixels, clipped or not
+    #[test]
+    fn test_circle_clipped_walk() {
+        let area = ClipRect::new(-20, 30, 60, 90);
+        for (center, radius) in [
+            (Point::new(20, 60), 45),
+            (Point::new(20, 60 + 2_000), 2_000),
+            (Point::new(-3_000, 50), 3_010),
+            (Point::new(5_000, 5_000), 7_000),
+        ] {
+            let circle = Circle::new(&center, radius).with_dash(Dash::new(&[7.0, 3.0], 0.0));
+            let mut clipped = Recording::with_bounds(area);
+            circle.draw(&mut clipped);
+
+            let mut full = Recording::new();
+            circle.draw(&mut full);
+            let inside: HashSet<(i32, i32)> = full
+                .touched()
+                .into_iter()
+                .filter(|&(x, y)| area.contains(x, y))
+                .collect();
+            assert_eq!(clipped.touched(), inside, "radius {radius}");
+        }
+    }
 
     #[test]
     fn test_circle_new() {
@@ -137,13 +339,10 @@ mod tests {
     fn test_circle_draw() {
         let center = Point::new(100, 100);
         let radius = 50;
-        let circle = Circle::new(&center, radius);
-
-        let mut image = Image::blank(800, 800);
-        circle.draw(&mut image);
+        let circle = Circle::new(&center, radius).with_stroke(Color::red());
 
-        // Verify that the image was modified
-        // assert_ne!(image.get_pixel(100, 100).unwrap(), Color::rgb(0, 0, 0));
+        let image = golden::render(200, 200, &[&circle]);
+        golden::assert_golden("circle", &image, Tolerance::EXACT);
     }
 
     #[test]
@@ -153,9 +352,25 @@ mod tests {
         let circle = Circle::new(&center, radius);
 
         let color = circle.color();
-        assert!(color.r >= 50 && color.r <= 200);
-        assert!(color.g >= 50 && color.g <= 200);
-        assert!(color.b >= 50 && color.b <= 200);
+        assert_eq!((color.r, color.g, color.b), (255, 255, 255));
+    }
+
+    #[test]
+    fn test_circle_to_polygons() {
+        let center = Point::new(100, 100);
+        let circle = Circle::new(&center, 40);
+        let polygons = circle.to_polygons();
+
+        assert_eq!(polygons.len(), 1);
+        assert!(polygons[0].vertices().len() >= 8);
+        for vertex in polygons[0].vertices() {
+            let dx = (vertex.x - center.x) as f64;
+            let dy = (vertex.y - center.y) as f64;
+            assert!(((dx * dx + dy * dy).sqrt() - 40.0).abs() <= 1.0);
+        }
+
+        // Degenerate circles flatten to nothing
+        assert!(Circle::flatten(&center, 0).is_empty());
     }
 
     #[test]
@@ -175,4 +390,87 @@ mod tests {
         let circle = Circle::new(&center, 1000);
         assert_eq!(circle.circles.len(), 1);
     }
+
+    // Test circles entirely off the surface are skipped without plotting
+    #[test]
+    fn test_circle_early_rejection() {
+        struct Counter(usize);
+        impl Displayable for Counter {
+            fn display(&mut self, _x: i32, _y: i32, _color: Color) {
+                self.0 += 1;
+            }
+            fn bounds(&self) -> Option<ClipRect> {
+                Some(ClipRect::from_size(100, 100))
+            }
+        }
+
+        let circle = Circle::new(&Point::new(5000, 5000), 1000);
+        let area = circle.bounding_box().unwrap();
+        assert_eq!((area.left, area.right), (3999, 6001));
+
+        let mut counter = Counter(0);
+        circle.draw(&mut counter);
+        assert_eq!(counter.0, 0);
+
+        // A circle crossing the surface still has to be drawn
+        Circle::new(&Point::new(50, 50), 60).draw(&mut counter);
+        assert!(counter.0 > 0);
+    }
+
+    proptest! {
+        // Property: the outline maps onto itself under all eight octant symmetries
+        #[test]
+        fn prop_circle_octant_symmetry(cx in -1000..1000, cy in -1000..1000, radius in 0..150) {
+            let mut pixels = Recording::new();
+            Circle::new(&Point::new(cx, cy), radius).draw(&mut pixels);
+
+            prop_assert!(!pixels.touched().is_empty());
+            for &(x, y) in &pixels.touched() {
+                let (dx, dy) = (x - cx, y - cy);
+                for (sx, sy) in [
+                    (dx, dy), (dy, dx), (-dy, dx), (-dx, dy),
+                    (-dx, -dy), (-dy, -dx), (dy, -dx), (dx, -dy),
+                ] {
+                    prop_assert!(pixels.count(cx + sx, cy + sy) > 0);
+                }
+            }
+        }
+
+        // Property: circles at the edge of the coordinate space draw without overflowing
+        #[test]
+        fn prop_circle_extremes(
+            cx in prop_oneof![Just(i32::MAX), Just(i32::MIN), any::<i32>()],
+            cy in prop_oneof![Just(i32::MAX), Just(i32::MIN), any::<i32>()],
+            radius in prop_oneof![-300..300, Just(i32::MIN)],
+        ) {
+            let mut pixels = Recording::new();
+            Circle::new(&Point::new(cx, cy), radius).draw(&mut pixels);
+
+            let area = Circle::new(&Point::new(cx, cy), radius).bounding_box().unwrap();
+            prop_assert!(pixels.touched().iter().all(|&(x, y)| area.contains(x, y)));
+        }
+    }
+
+    // Test dashes follow the circumference without gaps at octant boundaries
+    #[test]
+    fn test_circle_dash() {
+        let center = Point::new(0, 0);
+        let mut solid = Recording::new();
+        Circle::new(&center, 40).draw(&mut solid);
+        let mut dashed = Recording::new();
+        Circle::new(&center, 40)
+            .with_dash(Dash::new(&[10.0, 10.0], 0.0))
+            .draw(&mut dashed);
+
+        let (solid, dashed) = (solid.touched(), dashed.touched());
+        assert!(dashed.is_subset(&solid));
+        let ratio = dashed.len() as f64 / solid.len() as f64;
+        assert!((0.4..0.6).contains(&ratio), "{ratio}");
+
+        // Dashes are measured from the positive x axis towards positive y,
+        // so (39, 8) is 8px into the first dash and (37, 15) sits in the first gap
+        assert!(dashed.contains(&(40, 0)));
+        assert!(dashed.contains(&(39, 8)));
+        assert!(!dashed.contains(&(37, 15)));
+    }
 }
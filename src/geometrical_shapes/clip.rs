@@ -0,0 +1,406 @@
+use super::{Displayable, Point, Polygonal};
+use raster::Color;
+
+/// Axis-aligned rectangle of pixels, bounds are inclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClipRect {
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+}
+
+#[allow(dead_code)]
+impl ClipRect {
+    /// Creates a rectangle from its inclusive corner coordinates
+    pub fn new(left: i32, top: i32, right: i32, bottom: i32) -> Self {
+        ClipRect {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    /// Creates the rectangle covering a `width` x `height` surface
+    pub fn from_size(width: i32, height: i32) -> Self {
+        ClipRect::new(0, 0, width - 1, height - 1)
+    }
+
+    /// Smallest rectangle containing every point, `None` when there are none
+    pub fn around<'p>(points: impl IntoIterator<Item = &'p Point>) -> Option<ClipRect> {
+        points
+            .into_iter()
+            .map(|p| ClipRect::new(p.x, p.y, p.x, p.y))
+            .reduce(|a, b| a.union(&b))
+    }
+
+    /// Checks whether the rectangle contains no pixels
+    pub fn is_empty(&self) -> bool {
+        self.left > self.right || self.top > self.bottom
+    }
+
+    /// Checks whether a pixel lies inside the rectangle
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.left && x <= self.right && y >= self.top && y <= self.bottom
+    }
+
+    /// Returns the overlap of two rectangles (possibly empty)
+    pub fn intersect(&self, other: &ClipRect) -> ClipRect {
+        ClipRect::new(
+            self.left.max(other.left),
+            self.top.max(other.top),
+            self.right.min(other.right),
+            self.bottom.min(other.bottom),
+        )
+    }
+
+    /// Checks whether two rectangles share at least one pixel
+    pub fn intersects(&self, other: &ClipRect) -> bool {
+        !self.intersect(other).is_empty()
+    }
+
+    /// Returns the smallest rectangle containing both rectangles
+    pub fn union(&self, other: &ClipRect) -> ClipRect {
+        ClipRect::new(
+            self.left.min(other.left),
+            self.top.min(other.top),
+            self.right.max(other.right),
+            self.bottom.max(other.bottom),
+        )
+    }
+
+    /// Grows the rectangle by `margin` pixels on every side
+    pub fn expand(&self, margin: i32) -> ClipRect {
+        ClipRect::new(
+            self.left.saturating_sub(margin),
+            self.top.saturating_sub(margin),
+            self.right.saturating_add(margin),
+            self.bottom.saturating_add(margin),
+        )
+    }
+
+    /// Cohen-Sutherland region code of a point relative to the rectangle
+    fn outcode(&self, x: f64, y: f64) -> u8 {
+        let mut code = 0;
+        if x < self.left as f64 {
+            code |= 1;
+        } else if x > self.right as f64 {
+            code |= 2;
+        }
+        if y < self.top as f64 {
+            code |= 4;
+        } else if y > self.bottom as f64 {
+            code |= 8;
+        }
+        code
+    }
+
+    /// Clips the segment `p0 -> p1` to the rectangle
+    /// Cohen-Sutherland outcodes settle trivially inside or outside segments,
+    /// the rest goes through Liang-Barsky parametric clipping
+    /// Returns the visible parameter range `(t0, t1)` within [0, 1]
+    pub fn clip_segment(&self, p0: (f64, f64), p1: (f64, f64)) -> Option<(f64, f64)> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let code0 = self.outcode(p0.0, p0.1);
+        let code1 = self.outcode(p1.0, p1.1);
+        if code0 | code1 == 0 {
+            return Some((0.0, 1.0));
+        }
+        if code0 & code1 != 0 {
+            return None;
+        }
+
+        let dx = p1.0 - p0.0;
+        let dy = p1.1 - p0.1;
+        let mut t0 = 0.0_f64;
+        let mut t1 = 1.0_f64;
+
+        for (p, q) in [
+            (-dx, p0.0 - self.left as f64),
+            (dx, self.right as f64 - p0.0),
+            (-dy, p0.1 - self.top as f64),
+            (dy, self.bottom as f64 - p0.1),
+        ] {
+            if p == 0.0 {
+                if q < 0.0 {
+                    return None;
+                }
+                continue;
+            }
+            let t = q / p;
+            if p < 0.0 {
+                t0 = t0.max(t);
+            } else {
+                t1 = t1.min(t);
+            }
+            if t0 > t1 {
+                return None;
+            }
+        }
+
+        Some((t0, t1))
+    }
+}
+
+/// A region drawing can be confined to
+#[derive(Debug, Clone)]
+pub enum ClipMask {
+    /// Pixels inside an axis-aligned rectangle
+    Rect(ClipRect),
+    /// Pixels whose center lies inside closed outlines (even-odd rule)
+    Polygon(Vec<Vec<(f64, f64)>>),
+    /// Pixels within `radius` of a center point
+    Circle(i32, i32, i32),
+}
+
+impl ClipMask {
+    /// Checks whether a pixel is inside the mask
+    fn contains(&self, x: i32, y: i32) -> bool {
+        match self {
+            ClipMask::Rect(rect) => rect.contains(x, y),
+            ClipMask::Polygon(rings) => {
+                let (px, py) = (x as f64 + 0.5, y as f64 + 0.5);
+                let mut inside = false;
+                for ring in rings {
+                    for i in 0..ring.len() {
+                        let (x0, y0) = ring[i];
+                        let (x1, y1) = ring[(i + 1) % ring.len()];
+                        if (y0 > py) != (y1 > py) && px < x0 + (py - y0) * (x1 - x0) / (y1 - y0) {
+                            inside = !inside;
+                        }
+                    }
+                }
+                inside
+            }
+            ClipMask::Circle(cx, cy, radius) => {
+                let dx = x as i64 - *cx as i64;
+                let dy = y as i64 - *cy as i64;
+                dx * dx + dy * dy <= *radius as i64 * *radius as i64
+            }
+        }
+    }
+
+    /// Smallest rectangle enclosing the mask
+    fn bounds(&self) -> ClipRect {
+        match self {
+            ClipMask::Rect(rect) => *rect,
+            ClipMask::Polygon(rings) => {
+                let points = rings.iter().flatten();
+                let left = points.clone().map(|p| p.0).fold(f64::MAX, f64::min);
+                let right = points.clone().map(|p| p.0).fold(f64::MIN, f64::max);
+                let top = points.clone().map(|p| p.1).fold(f64::MAX, f64::min);
+                let bottom = points.map(|p| p.1).fold(f64::MIN, f64::max);
+                ClipRect::new(
+                    left.floor() as i32,
+                    top.floor() as i32,
+                    right.ceil() as i32,
+                    bottom.ceil() as i32,
+                )
+            }
+            ClipMask::Circle(cx, cy, radius) => ClipRect::new(*cx, *cy, *cx, *cy).expand(*radius),
+        }
+    }
+}
+
+/// Displayable surface confining every write to a stack of clip masks
+/// A pixel reaches the wrapped surface only when it lies inside all pushed masks,
+/// so nested panels can be clipped by pushing and popping regions
+pub struct ClipStack<'a> {
+    target: &'a mut dyn Displayable,
+    masks: Vec<ClipMask>,
+}
+
+#[allow(dead_code)]
+impl<'a> ClipStack<'a> {
+    /// Wraps a surface with an empty clip stack
+    pub fn new(target: &'a mut dyn Displayable) -> Self {
+        ClipStack {
+            target,
+            masks: Vec::new(),
+        }
+    }
+
+    /// Confines drawing to an axis-aligned rectangle
+    pub fn push_rect(&mut self, rect: ClipRect) {
+        self.masks.push(ClipMask::Rect(rect));
+    }
+
+    /// Confines drawing to a disc around `center`
+    pub fn push_circle(&mut self, center: &Point, radius: i32) {
+        self.masks
+            .push(ClipMask::Circle(center.x, center.y, radius));
+    }
+
+    /// Confines drawing to the interior of any polygonal shape
+    pub fn push_shape(&mut self, shape: &dyn Polygonal) {
+        let mut rings = Vec::new();
+        for polygon in shape.to_polygons() {
+            rings.push(
+                polygon
+                    .vertices()
+                    .iter()
+                    .map(|p| (p.x as f64, p.y as f64))
+                    .collect(),
+            );
+            for hole in polygon.holes() {
+                rings.push(hole.iter().map(|p| (p.x as f64, p.y as f64)).collect());
+            }
+        }
+        self.masks.push(ClipMask::Polygon(rings));
+    }
+
+    /// Removes the most recently pushed mask
+    pub fn pop(&mut self) -> Option<ClipMask> {
+        self.masks.pop()
+    }
+
+    /// Number of masks currently applied
+    pub fn depth(&self) -> usize {
+        self.masks.len()
+    }
+}
+
+impl Displayable for ClipStack<'_> {
+    /// Forwards the pixel only when every mask contains it
+    fn display(&mut self, x: i32, y: i32, color: Color) {
+        if self.masks.iter().all(|mask| mask.contains(x, y)) {
+            self.target.display(x, y, color);
+        }
+    }
+
+    /// Bounding rectangle of all masks and the wrapped surface
+    fn bounds(&self) -> Option<ClipRect> {
+        self.masks
+            .iter()
+            .map(ClipMask::bounds)
+            .chain(self.target.bounds())
+            .reduce(|a, b| a.intersect(&b))
+    }
+}
+
+// Unit tests for clipping
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometrical_shapes::Recording;
+    use crate::geometrical_shapes::{Circle, Drawable, Line, Pentagon};
+    use std::collections::HashSet;
+
+    // Test outcodes accept and reject segments trivially
+    #[test]
+    fn test_clip_segment_trivial() {
+        let rect = ClipRect::new(0, 0, 10, 10);
+
+        assert_eq!(rect.clip_segment((1.0, 1.0), (9.0, 9.0)), Some((0.0, 1.0)));
+        assert_eq!(rect.clip_segment((-5.0, -5.0), (-1.0, 20.0)), None);
+        assert_eq!(rect.clip_segment((20.0, 0.0), (30.0, 10.0)), None);
+    }
+
+    // Test partially visible segments are cut at the rectangle edges
+    #[test]
+    fn test_clip_segment_partial() {
+        let rect = ClipRect::new(0, 0, 10, 10);
+
+        let (t0, t1) = rect.clip_segment((-10.0, 5.0), (20.0, 5.0)).unwrap();
+        assert!((t0 - 1.0 / 3.0).abs() < 1e-9);
+        assert!((t1 - 2.0 / 3.0).abs() < 1e-9);
+
+        // Misses the corner entirely even though outcodes don't share a bit
+        assert_eq!(rect.clip_segment((-5.0, 8.0), (8.0, 21.0)), None);
+    }
+
+    // Test rectangle helpers
+    #[test]
+    fn test_clip_rect_helpers() {
+        let rect = ClipRect::from_size(100, 50);
+        assert_eq!(rect, ClipRect::new(0, 0, 99, 49));
+        assert!(rect.contains(99, 49));
+        assert!(!rect.contains(100, 0));
+
+        let other = ClipRect::new(90, 40, 200, 200);
+        assert_eq!(rect.intersect(&other), ClipRect::new(90, 40, 99, 49));
+        assert!(rect.intersects(&other));
+        assert!(!rect.intersects(&ClipRect::new(100, 0, 120, 10)));
+        assert_eq!(rect.union(&other), ClipRect::new(0, 0, 200, 200));
+        assert_eq!(
+            ClipRect::around(&[Point::new(5, -3), Point::new(-2, 8)]),
+            Some(ClipRect::new(-2, -3, 5, 8))
+        );
+        assert_eq!(ClipRect::around(&[]), None);
+        assert!(
+            rect.intersect(&ClipRect::new(200, 200, 300, 300))
+                .is_empty()
+        );
+        assert_eq!(
+            ClipRect::new(i32::MIN, 0, i32::MAX, 0).expand(5).left,
+            i32::MIN
+        );
+    }
+
+    // Test nested masks only let through their intersection
+    #[test]
+    fn test_clip_stack_nesting() {
+        let mut pixels = Recording::new();
+        {
+            let mut clip = ClipStack::new(&mut pixels);
+            clip.push_rect(ClipRect::new(0, 0, 9, 9));
+            clip.push_circle(&Point::new(0, 0), 5);
+            assert_eq!(clip.depth(), 2);
+            assert_eq!(clip.bounds(), Some(ClipRect::new(0, 0, 5, 5)));
+
+            Line::from_points(&Point::new(-20, 3), &Point::new(20, 3), 1, Color::white())
+                .draw(&mut clip);
+
+            assert!(matches!(clip.pop(), Some(ClipMask::Circle(0, 0, 5))));
+            Line::from_points(&Point::new(-20, 8), &Point::new(20, 8), 1, Color::white())
+                .draw(&mut clip);
+        }
+
+        let mut expected: HashSet<(i32, i32)> = (0..=4).map(|x| (x, 3)).collect();
+        expected.extend((0..=9).map(|x| (x, 8)));
+        assert_eq!(pixels.touched(), expected);
+    }
+
+    // Test polygon masks follow the shape's interior
+    #[test]
+    fn test_clip_stack_shape() {
+        let mut pixels = Recording::new();
+        {
+            let mut clip = ClipStack::new(&mut pixels);
+            clip.push_shape(&Pentagon::new(&Point::new(50, 50), 20));
+            Circle::new(&Point::new(50, 50), 100).draw(&mut clip);
+            Line::from_points(&Point::new(0, 50), &Point::new(100, 50), 1, Color::white())
+                .draw(&mut clip);
+        }
+
+        assert!(!pixels.touched().is_empty());
+        for (x, y) in &pixels.touched() {
+            assert_eq!(*y, 50);
+            assert!((30..=70).contains(x));
+        }
+    }
+
+    // Test huge lines only walk the clipped portion
+    #[test]
+    fn test_clip_stack_huge_line() {
+        let mut pixels = Recording::new();
+        {
+            let mut clip = ClipStack::new(&mut pixels);
+            clip.push_rect(ClipRect::new(0, 0, 9, 9));
+            let far = Line::from_points(
+                &Point::new(i32::MIN, 5),
+                &Point::new(i32::MAX, 5),
+                1,
+                Color::white(),
+            );
+            far.draw(&mut clip);
+        }
+
+        let expected: HashSet<(i32, i32)> = (0..=9).map(|x| (x, 5)).collect();
+        assert_eq!(pixels.touched(), expected);
+    }
+}
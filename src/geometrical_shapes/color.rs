@@ -0,0 +1,914 @@
+use super::Gradient;
+use super::paint::lerp;
+use rand::Rng;
+use raster::Color;
+use std::fmt;
+
+/// Hue, saturation and lightness
+/// Hue is in degrees from 0.0 to 360.0, saturation and lightness from 0.0 to 1.0
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsl {
+    pub h: f64,
+    pub s: f64,
+    pub l: f64,
+}
+
+/// Hue, saturation and value
+/// Hue is in degrees from 0.0 to 360.0, saturation and value from 0.0 to 1.0
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsv {
+    pub h: f64,
+    pub s: f64,
+    pub v: f64,
+}
+
+/// CIE L*a*b* under the D65 white point
+/// Lightness runs from 0.0 to 100.0, `a` from green to red and `b` from blue to yellow
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lab {
+    pub l: f64,
+    pub a: f64,
+    pub b: f64,
+}
+
+/// Björn Ottosson's Oklab, a perceptual space where equal steps look equally far apart
+/// Lightness runs from 0.0 to 1.0, `a` from green to red and `b` from blue to yellow
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Oklab {
+    pub l: f64,
+    pub a: f64,
+    pub b: f64,
+}
+
+/// Color space gradients and blends are interpolated in
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Interpolation {
+    /// Straight between the sRGB channels, cheap but muddy in the middle
+    #[default]
+    Srgb,
+    /// Between linear light channels, as light would physically mix
+    LinearRgb,
+    /// Through CIE L*a*b*
+    Lab,
+    /// Through Oklab, the most even to the eye
+    Oklab,
+}
+
+/// Channels scaled to 0.0..=1.0
+fn unit(color: &Color) -> (f64, f64, f64) {
+    (
+        color.r as f64 / 255.0,
+        color.g as f64 / 255.0,
+        color.b as f64 / 255.0,
+    )
+}
+
+/// Channel scaled back to 0..=255
+fn byte(value: f64) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// Hue in degrees of a color with the given channels, largest channel and chroma
+fn hue(r: f64, g: f64, b: f64, max: f64, chroma: f64) -> f64 {
+    if chroma == 0.0 {
+        0.0
+    } else if max == r {
+        (60.0 * (g - b) / chroma).rem_euclid(360.0)
+    } else if max == g {
+        60.0 * (b - r) / chroma + 120.0
+    } else {
+        60.0 * (r - g) / chroma + 240.0
+    }
+}
+
+/// Color with the given hue and chroma, lifted by `m` on every channel
+fn from_hue(h: f64, chroma: f64, m: f64) -> (f64, f64, f64) {
+    let h = h.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as i32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    (r + m, g + m, b + m)
+}
+
+#[allow(dead_code)]
+impl Hsl {
+    pub fn new(h: f64, s: f64, l: f64) -> Self {
+        Hsl { h, s, l }
+    }
+
+    /// Converts an sRGB color, ignoring alpha
+    pub fn from_color(color: &Color) -> Self {
+        let (r, g, b) = unit(color);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let chroma = max - min;
+        let l = (max + min) / 2.0;
+        let s = if chroma == 0.0 {
+            0.0
+        } else {
+            chroma / (1.0 - (2.0 * l - 1.0).abs())
+        };
+        Hsl::new(hue(r, g, b, max, chroma), s, l)
+    }
+
+    /// Converts to an opaque sRGB color
+    pub fn to_color(self) -> Color {
+        let (s, l) = (self.s.clamp(0.0, 1.0), self.l.clamp(0.0, 1.0));
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let (r, g, b) = from_hue(self.h, chroma, l - chroma / 2.0);
+        Color::rgb(byte(r), byte(g), byte(b))
+    }
+}
+
+#[allow(dead_code)]
+impl Hsv {
+    pub fn new(h: f64, s: f64, v: f64) -> Self {
+        Hsv { h, s, v }
+    }
+
+    /// Converts an sRGB color, ignoring alpha
+    pub fn from_color(color: &Color) -> Self {
+        let (r, g, b) = unit(color);
+        let max = r.max(g).max(b);
+        let chroma = max - r.min(g).min(b);
+        let s = if max == 0.0 { 0.0 } else { chroma / max };
+        Hsv::new(hue(r, g, b, max, chroma), s, max)
+    }
+
+    /// Converts to an opaque sRGB color
+    pub fn to_color(self) -> Color {
+        let (s, v) = (self.s.clamp(0.0, 1.0), self.v.clamp(0.0, 1.0));
+        let chroma = v * s;
+        let (r, g, b) = from_hue(self.h, chroma, v - chroma);
+        Color::rgb(byte(r), byte(g), byte(b))
+    }
+}
+
+/// D65 reference white in XYZ
+const WHITE: (f64, f64, f64) = (0.95047, 1.0, 1.08883);
+
+/// Undoes the sRGB transfer curve
+pub(super) fn to_linear(channel: f64) -> f64 {
+    if channel <= 0.04045 {
+        channel / 12.92
+    } else {
+        ((channel + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Applies the sRGB transfer curve
+pub(super) fn from_linear(channel: f64) -> f64 {
+    if channel <= 0.0031308 {
+        channel * 12.92
+    } else {
+        1.055 * channel.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+#[allow(dead_code)]
+impl Lab {
+    pub fn new(l: f64, a: f64, b: f64) -> Self {
+        Lab { l, a, b }
+    }
+
+    /// Converts an sRGB color, ignoring alpha
+    pub fn from_color(color: &Color) -> Self {
+        let (r, g, b) = unit(color);
+        let (r, g, b) = (to_linear(r), to_linear(g), to_linear(b));
+        let x = 0.4124564 * r + 0.3575761 * g + 0.1804375 * b;
+        let y = 0.2126729 * r + 0.7151522 * g + 0.0721750 * b;
+        let z = 0.0193339 * r + 0.1191920 * g + 0.9503041 * b;
+
+        let f = |t: f64| {
+            if t > 216.0 / 24389.0 {
+                t.cbrt()
+            } else {
+                (24389.0 / 27.0 * t + 16.0) / 116.0
+            }
+        };
+        let (fx, fy, fz) = (f(x / WHITE.0), f(y / WHITE.1), f(z / WHITE.2));
+        Lab::new(116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+    }
+
+    /// Converts to an opaque sRGB color, clipping colors outside the sRGB gamut
+    pub fn to_color(self) -> Color {
+        let fy = (self.l + 16.0) / 116.0;
+        let fx = fy + self.a / 500.0;
+        let fz = fy - self.b / 200.0;
+        let f = |t: f64| {
+            if t > 6.0 / 29.0 {
+                t * t * t
+            } else {
+                (116.0 * t - 16.0) * 27.0 / 24389.0
+            }
+        };
+        let (x, y, z) = (f(fx) * WHITE.0, f(fy) * WHITE.1, f(fz) * WHITE.2);
+
+        let r = 3.2404542 * x - 1.5371385 * y - 0.4985314 * z;
+        let g = -0.9692660 * x + 1.8760108 * y + 0.0415560 * z;
+        let b = 0.0556434 * x - 0.2040259 * y + 1.0572252 * z;
+        Color::rgb(
+            byte(from_linear(r)),
+            byte(from_linear(g)),
+            byte(from_linear(b)),
+        )
+    }
+}
+
+#[allow(dead_code)]
+impl Oklab {
+    pub fn new(l: f64, a: f64, b: f64) -> Self {
+        Oklab { l, a, b }
+    }
+
+    /// Converts an sRGB color, ignoring alpha
+    pub fn from_color(color: &Color) -> Self {
+        let (r, g, b) = unit(color);
+        let (r, g, b) = (to_linear(r), to_linear(g), to_linear(b));
+        let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+        let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+        let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+
+        Oklab::new(
+            0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+            1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+            0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+        )
+    }
+
+    /// Converts to an opaque sRGB color, clipping colors outside the sRGB gamut
+    pub fn to_color(self) -> Color {
+        let l = (self.l + 0.3963377774 * self.a + 0.2158037573 * self.b).powi(3);
+        let m = (self.l - 0.1055613458 * self.a - 0.0638541728 * self.b).powi(3);
+        let s = (self.l - 0.0894841775 * self.a - 1.2914855480 * self.b).powi(3);
+
+        let r = 4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s;
+        let g = -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s;
+        let b = -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s;
+        Color::rgb(
+            byte(from_linear(r)),
+            byte(from_linear(g)),
+            byte(from_linear(b)),
+        )
+    }
+}
+
+impl Interpolation {
+    /// Blends `a` towards `b` by `t` from 0.0 to 1.0 in this space
+    /// Alpha is always blended linearly
+    pub fn mix(self, a: &Color, b: &Color, t: f64) -> Color {
+        let between = |a: f64, b: f64| a + (b - a) * t;
+        let mut color = match self {
+            Interpolation::Srgb => return lerp(a, b, t),
+            Interpolation::LinearRgb => {
+                let ((ar, ag, ab), (br, bg, bb)) = (unit(a), unit(b));
+                let channel =
+                    |a: f64, b: f64| byte(from_linear(between(to_linear(a), to_linear(b))));
+                Color::rgb(channel(ar, br), channel(ag, bg), channel(ab, bb))
+            }
+            Interpolation::Lab => {
+                let (a, b) = (Lab::from_color(a), Lab::from_color(b));
+                Lab::new(between(a.l, b.l), between(a.a, b.a), between(a.b, b.b)).to_color()
+            }
+            Interpolation::Oklab => {
+                let (a, b) = (Oklab::from_color(a), Oklab::from_color(b));
+                Oklab::new(between(a.l, b.l), between(a.a, b.a), between(a.b, b.b)).to_color()
+            }
+        };
+        color.a = between(a.a as f64, b.a as f64).round() as u8;
+        color
+    }
+}
+
+impl From<Hsl> for Color {
+    fn from(hsl: Hsl) -> Self {
+        hsl.to_color()
+    }
+}
+
+impl From<Hsv> for Color {
+    fn from(hsv: Hsv) -> Self {
+        hsv.to_color()
+    }
+}
+
+impl From<Oklab> for Color {
+    fn from(oklab: Oklab) -> Self {
+        oklab.to_color()
+    }
+}
+
+impl From<Lab> for Color {
+    fn from(lab: Lab) -> Self {
+        lab.to_color()
+    }
+}
+
+/// Reasons a piece of text is not a color
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColorError {
+    /// Starts with `#` but is not 3, 4, 6 or 8 hex digits
+    InvalidHex(String),
+    /// Not one of the CSS named colors
+    UnknownName(String),
+}
+
+impl fmt::Display for ColorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ColorError::InvalidHex(text) => write!(f, "invalid hex color: {text}"),
+            ColorError::UnknownName(text) => write!(f, "unknown color name: {text}"),
+        }
+    }
+}
+
+/// Parses `#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa` or a CSS color name
+/// Names are matched ignoring case and surrounding whitespace
+#[allow(dead_code)]
+pub fn parse_color(text: &str) -> Result<Color, ColorError> {
+    let text = text.trim();
+    let Some(digits) = text.strip_prefix('#') else {
+        let name = text.to_ascii_lowercase();
+        return NAMED_COLORS
+            .binary_search_by(|(candidate, _)| candidate.cmp(&name.as_str()))
+            .map(|i| {
+                let rgb = NAMED_COLORS[i].1;
+                let alpha = if name == "transparent" { 0 } else { 255 };
+                Color::rgba((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8, alpha)
+            })
+            .map_err(|_| ColorError::UnknownName(text.to_string()));
+    };
+
+    let invalid = || ColorError::InvalidHex(text.to_string());
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    let nibble = |i: usize| u8::from_str_radix(&digits[i..=i], 16).unwrap() * 17;
+    let pair = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).unwrap();
+
+    match digits.len() {
+        3 => Ok(Color::rgb(nibble(0), nibble(1), nibble(2))),
+        4 => Ok(Color::rgba(nibble(0), nibble(1), nibble(2), nibble(3))),
+        6 => Ok(Color::rgb(pair(0), pair(2), pair(4))),
+        8 => Ok(Color::rgba(pair(0), pair(2), pair(4), pair(6))),
+        _ => Err(invalid()),
+    }
+}
+
+/// Formats a color as `#rrggbb`, or `#rrggbbaa` when it is not opaque
+#[allow(dead_code)]
+pub fn to_hex(color: &Color) -> String {
+    if color.a == 255 {
+        format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+    } else {
+        format!(
+            "#{:02x}{:02x}{:02x}{:02x}",
+            color.r, color.g, color.b, color.a
+        )
+    }
+}
+
+/// What a palette is meant to encode
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteKind {
+    /// Distinct colors for unrelated categories, with no order
+    Categorical,
+    /// Light to dark, for values running from low to high
+    Sequential,
+    /// Two hues meeting at a neutral midpoint, for values around a center
+    Diverging,
+}
+
+/// An ordered list of colors that work together
+#[derive(Debug, Clone)]
+pub struct Palette {
+    kind: PaletteKind,
+    colors: Vec<Color>,
+}
+
+/// Builds a palette from `0xrrggbb` values
+fn palette(kind: PaletteKind, values: &[u32]) -> Palette {
+    let colors = values
+        .iter()
+        .map(|rgb| Color::rgb((rgb >> 16) as u8, (rgb >> 8) as u8, *rgb as u8))
+        .collect();
+    Palette { kind, colors }
+}
+
+#[allow(dead_code)]
+impl Palette {
+    /// Creates a palette from any colors
+    pub fn new(kind: PaletteKind, colors: &[Color]) -> Self {
+        Palette {
+            kind,
+            colors: colors.to_vec(),
+        }
+    }
+
+    /// Tableau 10, a balanced categorical palette
+    pub fn tableau10() -> Self {
+        palette(
+            PaletteKind::Categorical,
+            &[
+                0x4e79a7, 0xf28e2b, 0xe15759, 0x76b7b2, 0x59a14f, 0xedc948, 0xb07aa1, 0xff9da7,
+                0x9c755f, 0xbab0ac,
+            ],
+        )
+    }
+
+    /// Okabe-Ito, a categorical palette that stays distinct under color blindness
+    pub fn okabe_ito() -> Self {
+        palette(
+            PaletteKind::Categorical,
+            &[
+                0xe69f00, 0x56b4e9, 0x009e73, 0xf0e442, 0x0072b2, 0xd55e00, 0xcc79a7, 0x000000,
+            ],
+        )
+    }
+
+    /// ColorBrewer Set2, soft categorical colors
+    pub fn set2() -> Self {
+        palette(
+            PaletteKind::Categorical,
+            &[
+                0x66c2a5, 0xfc8d62, 0x8da0cb, 0xe78ac3, 0xa6d854, 0xffd92f, 0xe5c494, 0xb3b3b3,
+            ],
+        )
+    }
+
+    /// ColorBrewer Blues, sequential from white to dark blue
+    pub fn blues() -> Self {
+        palette(
+            PaletteKind::Sequential,
+            &[
+                0xf7fbff, 0xdeebf7, 0xc6dbef, 0x9ecae1, 0x6baed6, 0x4292c6, 0x2171b5, 0x08519c,
+                0x08306b,
+            ],
+        )
+    }
+
+    /// ColorBrewer Greens, sequential from white to dark green
+    pub fn greens() -> Self {
+        palette(
+            PaletteKind::Sequential,
+            &[
+                0xf7fcf5, 0xe5f5e0, 0xc7e9c0, 0xa1d99b, 0x74c476, 0x41ab5d, 0x238b45, 0x006d2c,
+                0x00441b,
+            ],
+        )
+    }
+
+    /// ColorBrewer RdBu, diverging from red through white to blue
+    pub fn red_blue() -> Self {
+        palette(
+            PaletteKind::Diverging,
+            &[
+                0x67001f, 0xb2182b, 0xd6604d, 0xf4a582, 0xfddbc7, 0xf7f7f7, 0xd1e5f0, 0x92c5de,
+                0x4393c3, 0x2166ac, 0x053061,
+            ],
+        )
+    }
+
+    /// ColorBrewer BrBG, diverging from brown through white to teal
+    pub fn brown_teal() -> Self {
+        palette(
+            PaletteKind::Diverging,
+            &[
+                0x543005, 0x8c510a, 0xbf812d, 0xdfc27d, 0xf6e8c3, 0xf5f5f5, 0xc7eae5, 0x80cdc1,
+                0x35978f, 0x01665e, 0x003c30,
+            ],
+        )
+    }
+
+    /// Looks up a curated palette by name, such as `"tableau10"` or `"blues"`
+    pub fn named(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "tableau10" => Some(Palette::tableau10()),
+            "okabe_ito" => Some(Palette::okabe_ito()),
+            "set2" => Some(Palette::set2()),
+            "blues" => Some(Palette::blues()),
+            "greens" => Some(Palette::greens()),
+            "red_blue" => Some(Palette::red_blue()),
+            "brown_teal" => Some(Palette::brown_teal()),
+            _ => None,
+        }
+    }
+
+    /// Generates `count` categorical colors sharing one saturation and lightness,
+    /// with hues a golden angle apart from a random start so neighbours never clash
+    pub fn random(count: usize) -> Self {
+        Palette::random_with(count, &mut rand::thread_rng())
+    }
+
+    /// Same as `random`, drawing from the given generator
+    pub fn random_with(count: usize, rng: &mut impl Rng) -> Self {
+        const GOLDEN_ANGLE: f64 = 137.50776405003785;
+
+        let start = rng.gen_range(0.0..360.0);
+        let s = rng.gen_range(0.55..0.75);
+        let l = rng.gen_range(0.5..0.65);
+        let colors = (0..count)
+            .map(|i| Hsl::new(start + GOLDEN_ANGLE * i as f64, s, l).to_color())
+            .collect();
+
+        Palette {
+            kind: PaletteKind::Categorical,
+            colors,
+        }
+    }
+
+    pub fn kind(&self) -> PaletteKind {
+        self.kind
+    }
+
+    pub fn colors(&self) -> &[Color] {
+        &self.colors
+    }
+
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    /// Color at `index`, cycling back to the start past the last one
+    /// Empty palettes give black
+    pub fn color(&self, index: usize) -> Color {
+        if self.colors.is_empty() {
+            return Color::black();
+        }
+        self.colors[index % self.colors.len()].clone()
+    }
+
+    /// Spreads the colors evenly along a gradient, first at 0.0 and last at 1.0
+    pub fn gradient(&self) -> Gradient {
+        let last = self.colors.len().saturating_sub(1).max(1) as f64;
+        let stops: Vec<(f64, Color)> = self
+            .colors
+            .iter()
+            .enumerate()
+            .map(|(i, color)| (i as f64 / last, color.clone()))
+            .collect();
+        Gradient::new(&stops)
+    }
+
+    /// Color at position `t` from 0.0 to 1.0, blending neighbouring colors
+    pub fn sample(&self, t: f64) -> Color {
+        self.gradient().color_at(t)
+    }
+}
+
+/// CSS named colors as `0xrrggbb`, sorted by name for binary search
+const NAMED_COLORS: [(&str, u32); 149] = [
+    ("aliceblue", 0xf0f8ff),
+    ("antiquewhite", 0xfaebd7),
+    ("aqua", 0x00ffff),
+    ("aquamarine", 0x7fffd4),
+    ("azure", 0xf0ffff),
+    ("beige", 0xf5f5dc),
+    ("bisque", 0xffe4c4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xffebcd),
+    ("blue", 0x0000ff),
+    ("blueviolet", 0x8a2be2),
+    ("brown", 0xa52a2a),
+    ("burlywood", 0xdeb887),
+    ("cadetblue", 0x5f9ea0),
+    ("chartreuse", 0x7fff00),
+    ("chocolate", 0xd2691e),
+    ("coral", 0xff7f50),
+    ("cornflowerblue", 0x6495ed),
+    ("cornsilk", 0xfff8dc),
+    ("crimson", 0xdc143c),
+    ("cyan", 0x00ffff),
+    ("darkblue", 0x00008b),
+    ("darkcyan", 0x008b8b),
+    ("darkgoldenrod", 0xb8860b),
+    ("darkgray", 0xa9a9a9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xa9a9a9),
+    ("darkkhaki", 0xbdb76b),
+    ("darkmagenta", 0x8b008b),
+    ("darkolivegreen", 0x556b2f),
+    ("darkorange", 0xff8c00),
+    ("darkorchid", 0x9932cc),
+    ("darkred", 0x8b0000),
+    ("darksalmon", 0xe9967a),
+    ("darkseagreen", 0x8fbc8f),
+    ("darkslateblue", 0x483d8b),
+    ("darkslategray", 0x2f4f4f),
+    ("darkslategrey", 0x2f4f4f),
+    ("darkturquoise", 0x00ced1),
+    ("darkviolet", 0x9400d3),
+    ("deeppink", 0xff1493),
+    ("deepskyblue", 0x00bfff),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1e90ff),
+    ("firebrick", 0xb22222),
+    ("floralwhite", 0xfffaf0),
+    ("forestgreen", 0x228b22),
+    ("fuchsia", 0xff00ff),
+    ("gainsboro", 0xdcdcdc),
+    ("ghostwhite", 0xf8f8ff),
+    ("gold", 0xffd700),
+    ("goldenrod", 0xdaa520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xadff2f),
+    ("grey", 0x808080),
+    ("honeydew", 0xf0fff0),
+    ("hotpink", 0xff69b4),
+    ("indianred", 0xcd5c5c),
+    ("indigo", 0x4b0082),
+    ("ivory", 0xfffff0),
+    ("khaki", 0xf0e68c),
+    ("lavender", 0xe6e6fa),
+    ("lavenderblush", 0xfff0f5),
+    ("lawngreen", 0x7cfc00),
+    ("lemonchiffon", 0xfffacd),
+    ("lightblue", 0xadd8e6),
+    ("lightcoral", 0xf08080),
+    ("lightcyan", 0xe0ffff),
+    ("lightgoldenrodyellow", 0xfafad2),
+    ("lightgray", 0xd3d3d3),
+    ("lightgreen", 0x90ee90),
+    ("lightgrey", 0xd3d3d3),
+    ("lightpink", 0xffb6c1),
+    ("lightsalmon", 0xffa07a),
+    ("lightseagreen", 0x20b2aa),
+    ("lightskyblue", 0x87cefa),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xb0c4de),
+    ("lightyellow", 0xffffe0),
+    ("lime", 0x00ff00),
+    ("limegreen", 0x32cd32),
+    ("linen", 0xfaf0e6),
+    ("magenta", 0xff00ff),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66cdaa),
+    ("mediumblue", 0x0000cd),
+    ("mediumorchid", 0xba55d3),
+    ("mediumpurple", 0x9370db),
+    ("mediumseagreen", 0x3cb371),
+    ("mediumslateblue", 0x7b68ee),
+    ("mediumspringgreen", 0x00fa9a),
+    ("mediumturquoise", 0x48d1cc),
+    ("mediumvioletred", 0xc71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xf5fffa),
+    ("mistyrose", 0xffe4e1),
+    ("moccasin", 0xffe4b5),
+    ("navajowhite", 0xffdead),
+    ("navy", 0x000080),
+    ("oldlace", 0xfdf5e6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6b8e23),
+    ("orange", 0xffa500),
+    ("orangered", 0xff4500),
+    ("orchid", 0xda70d6),
+    ("palegoldenrod", 0xeee8aa),
+    ("palegreen", 0x98fb98),
+    ("paleturquoise", 0xafeeee),
+    ("palevioletred", 0xdb7093),
+    ("papayawhip", 0xffefd5),
+    ("peachpuff", 0xffdab9),
+    ("peru", 0xcd853f),
+    ("pink", 0xffc0cb),
+    ("plum", 0xdda0dd),
+    ("powderblue", 0xb0e0e6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xff0000),
+    ("rosybrown", 0xbc8f8f),
+    ("royalblue", 0x4169e1),
+    ("saddlebrown", 0x8b4513),
+    ("salmon", 0xfa8072),
+    ("sandybrown", 0xf4a460),
+    ("seagreen", 0x2e8b57),
+    ("seashell", 0xfff5ee),
+    ("sienna", 0xa0522d),
+    ("silver", 0xc0c0c0),
+    ("skyblue", 0x87ceeb),
+    ("slateblue", 0x6a5acd),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xfffafa),
+    ("springgreen", 0x00ff7f),
+    ("steelblue", 0x4682b4),
+    ("tan", 0xd2b48c),
+    ("teal", 0x008080),
+    ("thistle", 0xd8bfd8),
+    ("tomato", 0xff6347),
+    ("transparent", 0x000000),
+    ("turquoise", 0x40e0d0),
+    ("violet", 0xee82ee),
+    ("wheat", 0xf5deb3),
+    ("white", 0xffffff),
+    ("whitesmoke", 0xf5f5f5),
+    ("yellow", 0xffff00),
+    ("yellowgreen", 0x9acd32),
+];
+
+// Unit tests for color conversions, parsing and palettes
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    fn rgb(color: &Color) -> (u8, u8, u8) {
+        (color.r, color.g, color.b)
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 0.01
+    }
+
+    // Test HSL and HSV of the primaries and back
+    #[test]
+    fn test_color_hsl_hsv() {
+        let orange = Color::rgb(255, 128, 0);
+        let hsl = Hsl::from_color(&orange);
+        assert!(close(hsl.h, 30.12) && close(hsl.s, 1.0) && close(hsl.l, 0.5));
+        let hsv = Hsv::from_color(&orange);
+        assert!(close(hsv.h, 30.12) && close(hsv.s, 1.0) && close(hsv.v, 1.0));
+
+        assert_eq!(rgb(&Hsl::new(120.0, 1.0, 0.25).to_color()), (0, 128, 0));
+        assert_eq!(rgb(&Hsv::new(240.0, 1.0, 1.0).to_color()), (0, 0, 255));
+        assert_eq!(rgb(&Hsl::new(-60.0, 1.0, 0.5).into()), (255, 0, 255));
+
+        let gray = Hsl::from_color(&Color::rgb(128, 128, 128));
+        assert_eq!((gray.h, gray.s), (0.0, 0.0));
+    }
+
+    // Test Lab values against published references
+    #[test]
+    fn test_color_lab() {
+        let white = Lab::from_color(&Color::white());
+        assert!(close(white.l, 100.0) && white.a.abs() < 0.01 && white.b.abs() < 0.01);
+
+        let red = Lab::from_color(&Color::red());
+        assert!((red.l - 53.24).abs() < 0.05);
+        assert!((red.a - 80.09).abs() < 0.05);
+        assert!((red.b - 67.20).abs() < 0.05);
+
+        assert_eq!(rgb(&Lab::new(0.0, 0.0, 0.0).to_color()), (0, 0, 0));
+    }
+
+    // Test Oklab values against published references
+    #[test]
+    fn test_color_oklab() {
+        let white = Oklab::from_color(&Color::white());
+        assert!(close(white.l, 1.0) && white.a.abs() < 0.001 && white.b.abs() < 0.001);
+
+        let blue = Oklab::from_color(&Color::blue());
+        assert!((blue.l - 0.452).abs() < 0.001);
+        assert!((blue.a + 0.032).abs() < 0.001);
+        assert!((blue.b + 0.312).abs() < 0.001);
+    }
+
+    // Test blends in each space keep their ends and differ in the middle
+    #[test]
+    fn test_color_interpolation() {
+        let (red, green) = (Color::red(), Color::rgba(0, 255, 0, 0));
+        let spaces = [
+            Interpolation::Srgb,
+            Interpolation::LinearRgb,
+            Interpolation::Lab,
+            Interpolation::Oklab,
+        ];
+        for space in spaces {
+            assert_eq!(rgb(&space.mix(&red, &green, 0.0)), (255, 0, 0));
+            assert_eq!(rgb(&space.mix(&red, &green, 1.0)), (0, 255, 0));
+            assert_eq!(space.mix(&red, &green, 0.5).a, 128);
+        }
+
+        // The sRGB midpoint is a dark olive, the others stay brighter
+        assert_eq!(
+            rgb(&Interpolation::Srgb.mix(&red, &green, 0.5)),
+            (128, 128, 0)
+        );
+        assert_eq!(
+            rgb(&Interpolation::LinearRgb.mix(&red, &green, 0.5)),
+            (188, 188, 0)
+        );
+        let oklab = Interpolation::Oklab.mix(&red, &green, 0.5);
+        assert!(Oklab::from_color(&oklab).l > Oklab::from_color(&Color::rgb(128, 128, 0)).l);
+
+        let gradient =
+            Gradient::between(red.clone(), green.clone()).with_interpolation(Interpolation::Oklab);
+        assert_eq!(rgb(&gradient.color_at(0.5)), rgb(&oklab));
+    }
+
+    // Test every color survives a round trip through each space
+    #[test]
+    fn test_color_round_trip() {
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(15) {
+                for b in (0..=255).step_by(15) {
+                    let color = Color::rgb(r, g, b);
+                    assert_eq!(rgb(&Hsl::from_color(&color).to_color()), (r, g, b));
+                    assert_eq!(rgb(&Hsv::from_color(&color).to_color()), (r, g, b));
+                    assert_eq!(rgb(&Lab::from_color(&color).to_color()), (r, g, b));
+                    assert_eq!(rgb(&Oklab::from_color(&color).to_color()), (r, g, b));
+                }
+            }
+        }
+    }
+
+    // Test every hex form and what is rejected
+    #[test]
+    fn test_color_parse_hex() {
+        assert_eq!(rgb(&parse_color("#ff8000").unwrap()), (255, 128, 0));
+        assert_eq!(rgb(&parse_color("#F80").unwrap()), (255, 136, 0));
+        assert_eq!(parse_color("#11223344").unwrap().a, 0x44);
+        assert_eq!(parse_color("#1234").unwrap().a, 0x44);
+        assert_eq!(parse_color("  #000000 ").unwrap().a, 255);
+
+        for bad in ["#", "#12", "#12345", "#gggggg", "#ff00ff00ff"] {
+            assert_eq!(
+                parse_color(bad).unwrap_err(),
+                ColorError::InvalidHex(bad.to_string())
+            );
+        }
+        assert_eq!(to_hex(&Color::rgb(255, 128, 0)), "#ff8000");
+        assert_eq!(to_hex(&Color::rgba(1, 2, 3, 4)), "#01020304");
+    }
+
+    // Test CSS names ignore case and unknown names are reported
+    #[test]
+    fn test_color_parse_names() {
+        assert!(NAMED_COLORS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+
+        assert_eq!(rgb(&parse_color("rebeccapurple").unwrap()), (102, 51, 153));
+        assert_eq!(
+            rgb(&parse_color("CornflowerBlue").unwrap()),
+            (100, 149, 237)
+        );
+        assert_eq!(parse_color("transparent").unwrap().a, 0);
+        assert_eq!(
+            parse_color("blurple").unwrap_err().to_string(),
+            "unknown color name: blurple"
+        );
+    }
+
+    // Test palettes cycle by index and blend between neighbours
+    #[test]
+    fn test_color_palette() {
+        let blues = Palette::blues();
+        assert_eq!(blues.kind(), PaletteKind::Sequential);
+        assert_eq!(rgb(&blues.sample(0.0)), (0xf7, 0xfb, 0xff));
+        assert_eq!(rgb(&blues.sample(1.0)), (0x08, 0x30, 0x6b));
+        assert_eq!(rgb(&blues.sample(0.5)), rgb(&blues.color(4)));
+
+        let tableau = Palette::named("Tableau10").unwrap();
+        assert_eq!(tableau.len(), 10);
+        assert_eq!(rgb(&tableau.color(12)), rgb(&tableau.color(2)));
+        assert_eq!(
+            rgb(&Palette::red_blue().color(5)),
+            (0xf7, 0xf7, 0xf7),
+            "diverging palettes are neutral in the middle"
+        );
+        assert!(Palette::named("rainbow").is_none());
+        assert_eq!(
+            rgb(&Palette::new(PaletteKind::Categorical, &[]).color(3)),
+            (0, 0, 0)
+        );
+    }
+
+    // Test random palettes share saturation and lightness but spread their hues
+    #[test]
+    fn test_color_random_palette() {
+        let palette = Palette::random_with(6, &mut StdRng::seed_from_u64(7));
+        assert_eq!(palette.len(), 6);
+        assert_eq!(palette.kind(), PaletteKind::Categorical);
+
+        let hsl: Vec<Hsl> = palette.colors().iter().map(Hsl::from_color).collect();
+        let mut hues: Vec<f64> = hsl.iter().map(|c| c.h).collect();
+        hues.sort_by(f64::total_cmp);
+        let gaps = hues.windows(2).map(|w| w[1] - w[0]);
+        assert!(
+            gaps.chain([hues[0] + 360.0 - hues[5]])
+                .all(|gap| gap > 20.0)
+        );
+        assert!(hsl.iter().all(|c| (c.l - hsl[0].l).abs() < 0.02));
+
+        let again = Palette::random_with(6, &mut StdRng::seed_from_u64(7));
+        assert_eq!(
+            palette.colors().iter().map(rgb).collect::<Vec<_>>(),
+            again.colors().iter().map(rgb).collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,207 @@
+use super::{Gradient, Interpolation, Palette, PaletteKind};
+use raster::Color;
+
+/// Standard scientific colormaps, sampled by a value from 0.0 to 1.0
+/// Each map is stored as evenly spaced sRGB anchors and blended in Oklab between them
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colormap {
+    /// Dark purple through teal to yellow, the matplotlib default
+    Viridis,
+    /// Black through purple and coral to pale yellow
+    Magma,
+    /// Deep blue through magenta and orange to yellow
+    Plasma,
+    /// Navy through gray to yellow, readable with color vision deficiencies
+    Cividis,
+    /// Google's improved rainbow, dark blue through green to dark red
+    Turbo,
+}
+
+const VIRIDIS: [u32; 10] = [
+    0x440154, 0x482878, 0x3e4989, 0x31688e, 0x26828e, 0x1f9e89, 0x35b779, 0x6ece58, 0xb5de2b,
+    0xfde725,
+];
+
+const MAGMA: [u32; 10] = [
+    0x000004, 0x180f3d, 0x440f76, 0x721f81, 0x9e2f7f, 0xcd4071, 0xf1605d, 0xfd9668, 0xfeca8d,
+    0xfcfdbf,
+];
+
+const PLASMA: [u32; 10] = [
+    0x0d0887, 0x46039f, 0x7201a8, 0x9c179e, 0xbd3786, 0xd8576b, 0xed7953, 0xfb9f3a, 0xfdca26,
+    0xf0f921,
+];
+
+const CIVIDIS: [u32; 10] = [
+    0x00224e, 0x123570, 0x3b496c, 0x575d6d, 0x707173, 0x8a8779, 0xa69d75, 0xc4b56c, 0xe4cf5b,
+    0xfee838,
+];
+
+const TURBO: [u32; 15] = [
+    0x30123b, 0x4145ab, 0x4675ed, 0x39a2fc, 0x1bcfd4, 0x24eca6, 0x61fc6c, 0xa4fc3b, 0xd1e834,
+    0xf3c63a, 0xfe9b2d, 0xf36315, 0xd93806, 0xb11901, 0x7a0402,
+];
+
+/// Color of a `0xrrggbb` anchor
+fn anchor(rgb: u32) -> Color {
+    Color::rgb((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8)
+}
+
+#[allow(dead_code)]
+impl Colormap {
+    /// Every colormap, in declaration order
+    pub const ALL: [Colormap; 5] = [
+        Colormap::Viridis,
+        Colormap::Magma,
+        Colormap::Plasma,
+        Colormap::Cividis,
+        Colormap::Turbo,
+    ];
+
+    /// Looks a colormap up by name, ignoring case
+    pub fn named(name: &str) -> Option<Self> {
+        Colormap::ALL
+            .into_iter()
+            .find(|map| map.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Lowercase name of the colormap
+    pub fn name(self) -> &'static str {
+        match self {
+            Colormap::Viridis => "viridis",
+            Colormap::Magma => "magma",
+            Colormap::Plasma => "plasma",
+            Colormap::Cividis => "cividis",
+            Colormap::Turbo => "turbo",
+        }
+    }
+
+    fn anchors(self) -> &'static [u32] {
+        match self {
+            Colormap::Viridis => &VIRIDIS,
+            Colormap::Magma => &MAGMA,
+            Colormap::Plasma => &PLASMA,
+            Colormap::Cividis => &CIVIDIS,
+            Colormap::Turbo => &TURBO,
+        }
+    }
+
+    /// Color at `t`, clamped to 0.0..=1.0, with NaN mapped to 0.0
+    pub fn sample(self, t: f64) -> Color {
+        let anchors = self.anchors();
+        let last = anchors.len() - 1;
+        let position = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) } * last as f64;
+
+        let i = (position.floor() as usize).min(last - 1);
+        let (a, b) = (anchor(anchors[i]), anchor(anchors[i + 1]));
+        Interpolation::Oklab.mix(&a, &b, position - i as f64)
+    }
+
+    /// Color of `value` placed within `min..=max`, for heatmaps of raw data
+    /// An empty range maps everything to the start of the colormap
+    pub fn sample_range(self, value: f64, min: f64, max: f64) -> Color {
+        let span = max - min;
+        if span > 0.0 {
+            self.sample((value - min) / span)
+        } else {
+            self.sample(0.0)
+        }
+    }
+
+    /// The colormap as a gradient, for painting shapes with `Paint`
+    pub fn gradient(self) -> Gradient {
+        let last = (self.anchors().len() - 1) as f64;
+        let stops: Vec<(f64, Color)> = self
+            .anchors()
+            .iter()
+            .enumerate()
+            .map(|(i, rgb)| (i as f64 / last, anchor(*rgb)))
+            .collect();
+        Gradient::new(&stops).with_interpolation(Interpolation::Oklab)
+    }
+
+    /// `count` colors evenly spaced from one end of the colormap to the other
+    pub fn palette(self, count: usize) -> Palette {
+        let last = count.saturating_sub(1).max(1) as f64;
+        let colors: Vec<Color> = (0..count).map(|i| self.sample(i as f64 / last)).collect();
+        Palette::new(PaletteKind::Sequential, &colors)
+    }
+}
+
+// Unit tests for colormaps
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometrical_shapes::Oklab;
+
+    fn rgb(color: &Color) -> (u8, u8, u8) {
+        (color.r, color.g, color.b)
+    }
+
+    // Test the ends and midpoints match the published colormaps
+    #[test]
+    fn test_colormap_reference() {
+        assert_eq!(rgb(&Colormap::Viridis.sample(0.0)), (0x44, 0x01, 0x54));
+        assert_eq!(rgb(&Colormap::Viridis.sample(1.0)), (0xfd, 0xe7, 0x25));
+        assert_eq!(rgb(&Colormap::Magma.sample(0.0)), (0x00, 0x00, 0x04));
+        assert_eq!(rgb(&Colormap::Plasma.sample(1.0)), (0xf0, 0xf9, 0x21));
+        assert_eq!(rgb(&Colormap::Cividis.sample(0.0)), (0x00, 0x22, 0x4e));
+        assert_eq!(rgb(&Colormap::Turbo.sample(0.5)), (0xa4, 0xfc, 0x3b));
+
+        // Between anchors the colors stay close to matplotlib's full tables
+        let (r, g, b) = rgb(&Colormap::Viridis.sample(0.5));
+        assert!((r as i32 - 0x21).abs() <= 4, "{r}");
+        assert!((g as i32 - 0x91).abs() <= 4, "{g}");
+        assert!((b as i32 - 0x8c).abs() <= 4, "{b}");
+    }
+
+    // Test values outside 0.0..=1.0 are clamped and NaN starts the map
+    #[test]
+    fn test_colormap_clamp() {
+        for map in Colormap::ALL {
+            assert_eq!(rgb(&map.sample(-2.0)), rgb(&map.sample(0.0)));
+            assert_eq!(rgb(&map.sample(7.0)), rgb(&map.sample(1.0)));
+            assert_eq!(rgb(&map.sample(f64::NAN)), rgb(&map.sample(0.0)));
+            assert_eq!(rgb(&map.sample(f64::INFINITY)), rgb(&map.sample(1.0)));
+        }
+        let map = Colormap::Magma;
+        assert_eq!(
+            rgb(&map.sample_range(15.0, 10.0, 20.0)),
+            rgb(&map.sample(0.5))
+        );
+        assert_eq!(rgb(&map.sample_range(3.0, 1.0, 1.0)), rgb(&map.sample(0.0)));
+    }
+
+    // Test lightness only ever rises along the sequential maps
+    #[test]
+    fn test_colormap_monotonic() {
+        for map in [Colormap::Viridis, Colormap::Magma, Colormap::Cividis] {
+            let lightness: Vec<f64> = (0..=100)
+                .map(|i| Oklab::from_color(&map.sample(i as f64 / 100.0)).l)
+                .collect();
+            assert!(
+                lightness.windows(2).all(|pair| pair[1] >= pair[0] - 0.005),
+                "{}",
+                map.name()
+            );
+        }
+    }
+
+    // Test names, gradients and palettes agree with sampling
+    #[test]
+    fn test_colormap_gradient_palette() {
+        assert_eq!(Colormap::named(" Plasma "), Some(Colormap::Plasma));
+        assert_eq!(Colormap::named("jet"), None);
+
+        let map = Colormap::Turbo;
+        for t in [0.0, 0.1, 0.33, 0.5, 0.9, 1.0] {
+            assert_eq!(rgb(&map.gradient().color_at(t)), rgb(&map.sample(t)));
+        }
+
+        let palette = Colormap::Cividis.palette(5);
+        assert_eq!(palette.len(), 5);
+        assert_eq!(palette.kind(), PaletteKind::Sequential);
+        assert_eq!(rgb(&palette.color(4)), rgb(&Colormap::Cividis.sample(1.0)));
+    }
+}
@@ -1,31 +1,33 @@
-use super::{Drawable, Line, Point};
+use super::mesh::hull_points;
+use super::stroke::StrokeMask;
+use super::{Animatable, ClipRect, Displayable, Drawable, Point, Style, Styled};
 use rand::Rng;
-use raster::{Color, Image};
+use raster::Color;
 
 /// Represents a 3D cube in isometric projection
 /// Contains vertices, edges, and rendering properties
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Cubes {
-    cubes: Vec<(Point, i32, Color)>,
+    cubes: Vec<(Point, i32)>,
+    style: Style,
+    rotation: f64,
 }
 
 #[allow(dead_code)]
 impl Cubes {
     /// Creates a new cube with specified center and size
-    /// Generates a random color in moderate RGB range (50-200)
+    /// Kept for compatibility, same as `from_center`
     pub fn new(center: &Point, size: i32) -> Self {
-        let mut rng = rand::thread_rng();
-        let mut cubes = Vec::new();
-
-        let color = Color::rgb(
-            rng.gen_range(50..200),
-            rng.gen_range(50..200),
-            rng.gen_range(50..200),
-        );
-
-        cubes.push((center.clone(), size as i32, color));
+        Cubes::from_center(center, size)
+    }
 
-        Cubes { cubes }
+    /// Creates a cube with specified center and size, stroked 2px wide in white
+    pub fn from_center(center: &Point, size: i32) -> Self {
+        Cubes {
+            cubes: vec![(center.clone(), size)],
+            style: Style::new().width(2),
+            rotation: 0.0,
+        }
     }
 
     /// Generates a random cube within specified bounds
@@ -37,14 +39,41 @@ impl Cubes {
 
         let center = Point::random(width, height);
         let size = rng.gen_range(30..80);
-        let color = Color::rgb(
-            rng.gen_range(150..255),
-            rng.gen_range(150..255),
-            rng.gen_range(150..255),
-        );
-        cubes.push((center, size, color));
+        cubes.push((center, size));
+
+        Cubes {
+            cubes,
+            style: Style::random(150..255).width(2),
+            rotation: 0.0,
+        }
+    }
 
-        Cubes { cubes }
+    /// Turns every cube clockwise by `angle` radians around its center
+    pub fn with_rotation(mut self, angle: f64) -> Self {
+        self.rotation = angle;
+        self
+    }
+
+    /// Projected vertices of a cube, turned by the rotation
+    fn vertices(&self, center: &Point, size: i32) -> [Point; 8] {
+        let vertices = Cubes::get_isometric_projection(center, size);
+        if self.rotation == 0.0 {
+            return vertices;
+        }
+
+        let (sin, cos) = self.rotation.sin_cos();
+        vertices.map(|vertex| {
+            let dx = vertex.x as f64 - center.x as f64;
+            let dy = vertex.y as f64 - center.y as f64;
+            Point::new(
+                center
+                    .x
+                    .saturating_add((dx * cos - dy * sin).round() as i32),
+                center
+                    .y
+                    .saturating_add((dx * sin + dy * cos).round() as i32),
+            )
+        })
     }
 
     /// Calculates isometric projection vertices for cube rendering
@@ -53,26 +82,57 @@ impl Cubes {
         let x = center.x;
         let y = center.y;
         let s = size;
+        let h = s / 2;
+        // Saturate so cubes near the edge of the coordinate space cannot overflow
         [
-            Point::new(x - s, y - s / 2),
-            Point::new(x + s, y - s / 2),
-            Point::new(x - s, y + s / 2),
-            Point::new(x + s, y + s / 2),
-            Point::new(x - s / 2, y - s),
-            Point::new(x + s / 2, y - s),
-            Point::new(x - s / 2, y),
-            Point::new(x + s / 2, y),
+            Point::new(x.saturating_sub(s), y.saturating_sub(h)),
+            Point::new(x.saturating_add(s), y.saturating_sub(h)),
+            Point::new(x.saturating_sub(s), y.saturating_add(h)),
+            Point::new(x.saturating_add(s), y.saturating_add(h)),
+            Point::new(x.saturating_sub(h), y.saturating_sub(s)),
+            Point::new(x.saturating_add(h), y.saturating_sub(s)),
+            Point::new(x.saturating_sub(h), y),
+            Point::new(x.saturating_add(h), y),
         ]
     }
 }
 
+impl Styled for Cubes {
+    fn style(&self) -> &Style {
+        &self.style
+    }
+
+    fn style_mut(&mut self) -> &mut Style {
+        &mut self.style
+    }
+}
+
+impl Animatable for Cubes {
+    /// Moves, resizes and turns every cube and blends the style
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        Cubes {
+            cubes: self.cubes.interpolate(&to.cubes, t),
+            style: self.style.interpolate(&to.style, t),
+            rotation: self.rotation.interpolate(&to.rotation, t),
+        }
+    }
+}
+
 impl Drawable for Cubes {
     /// Renders cube using isometric projection
-    /// Draws 12 edges with consistent 2px thickness
+    /// Draws 12 edges with the style's width, each pixel once
+    /// A fill covers the cube's silhouette
     /// Implements Drawable trait requirement
-    fn draw(&self, image: &mut Image) {
-        for (center, size, color) in &self.cubes {
-            let vertices = Cubes::get_isometric_projection(center, *size);
+    fn draw(&self, image: &mut dyn Displayable) {
+        if !self.is_visible_on(image) {
+            return;
+        }
+
+        for (center, size) in &self.cubes {
+            let vertices = self.vertices(center, *size);
+            if self.style.fill.is_some() {
+                self.style.paint_fill(&[&hull_points(&vertices)], image);
+            }
 
             let edges = [
                 // Front face
@@ -92,22 +152,39 @@ impl Drawable for Cubes {
                 (3, 7),
             ];
 
-            let edge_thickness = 2;
-
+            let mut mask = StrokeMask::new(image).styled(&self.style);
             for (i, j) in edges.iter() {
-                let start = &vertices[*i];
-                let end = &vertices[*j];
-                let line = Line::from_points(start, end, edge_thickness, color.clone());
-
-                line.draw(image);
+                mask.add_path(
+                    &[vertices[*i].clone(), vertices[*j].clone()],
+                    self.style.width,
+                    false,
+                );
             }
+            self.style.paint_stroke(mask, image);
         }
     }
 
-    /// Returns a fallback color for cubes (black)
+    /// Returns the color of the cubes' style
     /// Implements Drawable trait requirement
     fn color(&self) -> Color {
-        Color::rgb(0, 0, 0)
+        self.style.color()
+    }
+
+    /// Covers the projected vertices of every cube
+    /// A turned cube reaches up to 1.12 times its size from the center
+    fn bounding_box(&self) -> Option<ClipRect> {
+        self.cubes
+            .iter()
+            .map(|(center, size)| {
+                let reach = if self.rotation == 0.0 {
+                    size.saturating_abs()
+                } else {
+                    (size.unsigned_abs() as f64 * 1.12).ceil() as i32
+                };
+                ClipRect::new(center.x, center.y, center.x, center.y)
+                    .expand(reach.saturating_add(self.style.reach().max(1)))
+            })
+            .reduce(|a, b| a.union(&b))
     }
 }
 
@@ -115,6 +192,7 @@ impl Drawable for Cubes {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::geometrical_shapes::golden::{self, Tolerance};
 
     #[test]
     fn test_cubes_new() {
@@ -123,13 +201,12 @@ mod tests {
         let cubes = Cubes::new(&center, size);
 
         assert_eq!(cubes.cubes.len(), 1);
-        let (cube_center, cube_size, color) = &cubes.cubes[0];
+        let (cube_center, cube_size) = &cubes.cubes[0];
+        let color = cubes.color();
         assert_eq!(cube_center.x, center.x);
         assert_eq!(cube_center.y, center.y);
         assert_eq!(*cube_size, size);
-        assert!(color.r >= 50 && color.r <= 200);
-        assert!(color.g >= 50 && color.g <= 200);
-        assert!(color.b >= 50 && color.b <= 200);
+        assert_eq!((color.r, color.g, color.b), (255, 255, 255));
     }
 
     #[test]
@@ -137,7 +214,7 @@ mod tests {
         let cubes = Cubes::random(800, 800);
 
         // Check each cube's properties
-        for (center, size, _color) in &cubes.cubes {
+        for (center, size) in &cubes.cubes {
             assert!(center.x >= 0 && center.x <= 800);
             assert!(center.y >= 0 && center.y <= 800);
             assert!(*size >= 30 && *size <= 80);
@@ -165,13 +242,10 @@ mod tests {
     fn test_cubes_draw() {
         let center = Point::new(100, 100);
         let size = 50;
-        let cubes = Cubes::new(&center, size);
+        let cubes = Cubes::new(&center, size).with_stroke(Color::rgb(0, 200, 255));
 
-        let mut image = Image::blank(800, 800);
-        cubes.draw(&mut image);
-
-        // Verify that the image was modified
-        // assert_ne!(image.get_pixel(100, 100).unwrap(), Color::rgb(0, 0, 0));
+        let image = golden::render(200, 200, &[&cubes]);
+        golden::assert_golden("cubes", &image, Tolerance::EXACT);
     }
 
     #[test]
@@ -181,9 +255,10 @@ mod tests {
         let cubes = Cubes::new(&center, size);
 
         let color = cubes.color();
-        assert_eq!(color.r, 0);
-        assert_eq!(color.g, 0);
-        assert_eq!(color.b, 0);
+        assert_eq!((color.r, color.g, color.b), (255, 255, 255));
+
+        let red = Cubes::new(&center, size).with_stroke(Color::red()).color();
+        assert_eq!((red.r, red.g, red.b), (255, 0, 0));
     }
 
     #[test]
@@ -203,4 +278,30 @@ mod tests {
         let cubes = Cubes::new(&center, 1000);
         assert_eq!(cubes.cubes.len(), 1);
     }
+
+    // Test rotation turns the vertices around the center and stays in the bounding box
+    #[test]
+    fn test_cubes_rotation() {
+        let center = Point::new(100, 100);
+        let still = Cubes::from_center(&center, 40);
+        let upright = Cubes::get_isometric_projection(&center, 40);
+        let coordinates = |points: &[Point]| points.iter().map(|p| (p.x, p.y)).collect::<Vec<_>>();
+        assert_eq!(
+            coordinates(&still.vertices(&center, 40)),
+            coordinates(&upright)
+        );
+
+        let half_turn = Cubes::from_center(&center, 40).with_rotation(std::f64::consts::PI);
+        for (turned, vertex) in half_turn.vertices(&center, 40).iter().zip(&upright) {
+            assert_eq!((turned.x, turned.y), (200 - vertex.x, 200 - vertex.y));
+        }
+
+        for step in 0..16 {
+            let cube = Cubes::from_center(&center, 40).with_rotation(step as f64 * 0.4);
+            let area = cube.bounding_box().unwrap();
+            let mut pixels = crate::geometrical_shapes::Recording::new();
+            cube.draw(&mut pixels);
+            assert!(pixels.touched().iter().all(|&(x, y)| area.contains(x, y)));
+        }
+    }
 }
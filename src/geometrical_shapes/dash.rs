@@ -0,0 +1,143 @@
+/// Dash pattern for strokes: alternating on and off lengths in pixels,
+/// measured along the whole outline so dashes run on around corners
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dash {
+    lengths: Vec<f64>,
+    phase: f64,
+}
+
+#[allow(dead_code)]
+impl Dash {
+    /// Creates a dash pattern from on/off lengths, starting `phase` pixels into it
+    /// An odd number of lengths is repeated to make the list even,
+    /// negative lengths count as zero
+    pub fn new(lengths: &[f64], phase: f64) -> Self {
+        let mut lengths: Vec<f64> = lengths.iter().map(|l| l.max(0.0)).collect();
+        if lengths.len() % 2 == 1 {
+            lengths.extend_from_within(..);
+        }
+        Dash { lengths, phase }
+    }
+
+    /// Single pixel dots with `gap` pixels between them
+    pub fn dotted(gap: f64) -> Self {
+        Dash::new(&[1.0, gap], 0.0)
+    }
+
+    /// Returns the on/off lengths
+    pub fn lengths(&self) -> &[f64] {
+        &self.lengths
+    }
+
+    /// Returns how far into the pattern the stroke starts
+    pub fn phase(&self) -> f64 {
+        self.phase
+    }
+
+    /// Checks whether the stroke is drawn `distance` pixels along the outline
+    /// Patterns with no length at all draw a solid stroke
+    pub fn is_on(&self, distance: f64) -> bool {
+        let period: f64 = self.lengths.iter().sum();
+        if period <= 0.0 || !period.is_finite() {
+            return true;
+        }
+
+        let mut position = (distance + self.phase).rem_euclid(period);
+        for (i, length) in self.lengths.iter().enumerate() {
+            if position < *length {
+                return i % 2 == 0;
+            }
+            position -= length;
+        }
+        false
+    }
+}
+
+// Unit tests for dash patterns
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drawn(dash: &Dash, count: i32) -> Vec<bool> {
+        (0..count).map(|d| dash.is_on(d as f64)).collect()
+    }
+
+    // Test on/off runs repeat with the pattern's period
+    #[test]
+    fn test_dash_is_on() {
+        let dash = Dash::new(&[3.0, 2.0], 0.0);
+        assert_eq!(
+            drawn(&dash, 10),
+            [
+                true, true, true, false, false, true, true, true, false, false
+            ]
+        );
+        assert!(dash.is_on(-3.0));
+        assert!(!dash.is_on(-1.0));
+    }
+
+    // Test the phase shifts where the pattern starts
+    #[test]
+    fn test_dash_phase() {
+        let dash = Dash::new(&[3.0, 2.0], 2.0);
+        assert_eq!(drawn(&dash, 5), [true, false, false, true, true]);
+        assert_eq!(dash.phase(), 2.0);
+    }
+
+    // Test odd length lists repeat and degenerate patterns stay solid
+    #[test]
+    fn test_dash_lengths() {
+        let odd = Dash::new(&[2.0, 1.0, 1.0], 0.0);
+        assert_eq!(odd.lengths(), &[2.0, 1.0, 1.0, 2.0, 1.0, 1.0]);
+        assert_eq!(
+            drawn(&odd, 8),
+            [true, true, false, true, false, false, true, false]
+        );
+
+        assert!(Dash::new(&[], 0.0).is_on(3.0));
+        assert!(Dash::new(&[0.0, 0.0], 0.0).is_on(3.0));
+        assert!(Dash::new(&[-4.0, -1.0], 0.0).is_on(3.0));
+
+        let dotted = Dash::dotted(3.0);
+        assert_eq!(
+            drawn(&dotted, 8),
+            [true, false, false, false, true, false, false, false]
+        );
+    }
+
+    // Test dashed outlines of every kind of shape against their golden image
+    #[test]
+    fn test_dash_golden() {
+        use crate::geometrical_shapes::golden::{self, Tolerance};
+        use crate::geometrical_shapes::{
+            Circle, Line, Pentagon, Point, Rectangle, Styled, Triangle,
+        };
+        use raster::Color;
+
+        let rectangle = Rectangle::new(&Point::new(0, 30), &Point::new(15, 10))
+            .with_dash(Dash::new(&[8.0, 4.0], 0.0));
+        let triangle = Triangle::new(
+            &Point::new(90, 50),
+            &Point::new(150, 50),
+            &Point::new(120, 10),
+        )
+        .with_dash(Dash::new(&[12.0, 3.0, 2.0, 3.0], 0.0));
+        let circle = Circle::new(&Point::new(40, 110), 30).with_dash(Dash::dotted(3.0));
+        let pentagon =
+            Pentagon::new(&Point::new(120, 110), 30).with_dash(Dash::new(&[5.0, 5.0], 2.5));
+        let line = Line::from_points(
+            &Point::new(10, 155),
+            &Point::new(150, 155),
+            2,
+            Color::white(),
+        )
+        .with_dash(Dash::new(&[10.0, 5.0], 0.0));
+
+        let image = golden::render(
+            160,
+            160,
+            &[&rectangle, &triangle, &circle, &pentagon, &line],
+        );
+        golden::assert_golden("dash", &image, Tolerance::EXACT);
+    }
+}
@@ -0,0 +1,238 @@
+use super::triangulation::ear_clip;
+use super::{Displayable, Drawable, Fillable, Point, Polygonal};
+use raster::Color;
+
+/// Strategy used to fill the interior of polygonal shapes
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FillStrategy {
+    /// Even-odd scanline fill over all outlines at once
+    #[default]
+    Scanline,
+    /// Ear-clipping triangulation, then each triangle filled separately
+    Triangulated,
+}
+
+/// Fills the region enclosed by `rings` using the even-odd rule
+/// Samples pixel centers row by row so adjacent regions sharing an edge
+/// never overlap, spans are clamped to the surface bounds
+#[allow(dead_code)]
+pub(super) fn fill_rings(rings: &[&[Point]], color: &Color, image: &mut dyn Displayable) {
+    let mut edges: Vec<(f64, f64, f64, f64)> = Vec::new();
+    for ring in rings {
+        for i in 0..ring.len() {
+            let a = &ring[i];
+            let b = &ring[(i + 1) % ring.len()];
+            if a.y == b.y {
+                continue;
+            }
+            // Store every edge top to bottom so shared edges intersect identically
+            let (top, bottom) = if a.y < b.y { (a, b) } else { (b, a) };
+            edges.push((top.x as f64, top.y as f64, bottom.x as f64, bottom.y as f64));
+        }
+    }
+    if edges.is_empty() {
+        return;
+    }
+
+    let mut min_y = edges.iter().map(|e| e.1).fold(f64::MAX, f64::min);
+    let mut max_y = edges.iter().map(|e| e.3).fold(f64::MIN, f64::max);
+    let (mut min_x, mut max_x) = (f64::MIN, f64::MAX);
+    if let Some(bounds) = image.bounds() {
+        min_y = min_y.max(bounds.top as f64);
+        max_y = max_y.min(bounds.bottom as f64 + 1.0);
+        min_x = bounds.left as f64;
+        max_x = bounds.right as f64 + 1.0;
+    }
+
+    let mut crossings: Vec<f64> = Vec::new();
+    for y in (min_y as i32)..(max_y.ceil() as i32) {
+        let sample = y as f64 + 0.5;
+
+        crossings.clear();
+        for &(x0, y0, x1, y1) in &edges {
+            if y0 <= sample && sample < y1 {
+                crossings.push(x0 + (sample - y0) * (x1 - x0) / (y1 - y0));
+            }
+        }
+        crossings.sort_by(f64::total_cmp);
+
+        for span in crossings.chunks_exact(2) {
+            let start = (span[0] - 0.5).ceil().max(min_x);
+            let end = (span[1] - 0.5).ceil().min(max_x);
+            if start < end {
+                image.display_span(start as i32, end as i32 - 1, y, color.clone());
+            }
+        }
+    }
+}
+
+impl<T: Polygonal> Fillable for T {
+    /// Fills every polygon of the shape with its own color
+    fn fill(&self, image: &mut dyn Displayable, strategy: FillStrategy) {
+        for polygon in self.to_polygons() {
+            let color = polygon.color();
+            match strategy {
+                FillStrategy::Scanline => {
+                    let mut rings: Vec<&[Point]> = vec![polygon.vertices()];
+                    rings.extend(polygon.holes().iter().map(|hole| hole.as_slice()));
+                    fill_rings(&rings, &color, image);
+                }
+                FillStrategy::Triangulated => {
+                    for triangle in ear_clip(polygon.vertices(), polygon.holes()) {
+                        fill_rings(&[&triangle], &color, image);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Unit tests for polygon filling
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometrical_shapes::Recording;
+    use crate::geometrical_shapes::ClipRect;
+    use crate::geometrical_shapes::{Pentagon, Polygon, Rectangle, Styled, Triangle};
+    use proptest::prelude::*;
+    use raster::Image;
+
+    fn filled_pixels(image: &Image) -> Vec<(i32, i32)> {
+        let mut pixels = Vec::new();
+        for y in 0..image.height {
+            for x in 0..image.width {
+                let pixel = image.get_pixel(x, y).unwrap();
+                if pixel.r != 0 || pixel.g != 0 || pixel.b != 0 {
+                    pixels.push((x, y));
+                }
+            }
+        }
+        pixels
+    }
+
+    fn points(coords: &[(i32, i32)]) -> Vec<Point> {
+        coords.iter().map(|&(x, y)| Point::new(x, y)).collect()
+    }
+
+    // Test a square fills exactly its width x height pixels
+    #[test]
+    fn test_fill_square() {
+        let square =
+            Polygon::from_vertices(&points(&[(2, 2), (12, 2), (12, 7), (2, 7)]), Color::white());
+
+        let mut image = Image::blank(20, 20);
+        square.fill(&mut image, FillStrategy::Scanline);
+
+        let pixels = filled_pixels(&image);
+        assert_eq!(pixels.len(), 50);
+        assert!(
+            pixels
+                .iter()
+                .all(|&(x, y)| (2..12).contains(&x) && (2..7).contains(&y))
+        );
+    }
+
+    // Test both strategies cover identical pixels for a concave shape with a hole
+    #[test]
+    fn test_fill_strategies_match() {
+        let outline = points(&[(1, 1), (30, 3), (18, 12), (29, 28), (2, 25)]);
+        let hole = points(&[(6, 8), (12, 8), (10, 18)]);
+        let polygon = Polygon::from_vertices(&outline, Color::white()).with_holes(vec![hole]);
+
+        let mut scanline = Image::blank(32, 32);
+        polygon.fill(&mut scanline, FillStrategy::Scanline);
+        let mut triangulated = Image::blank(32, 32);
+        polygon.fill(&mut triangulated, FillStrategy::Triangulated);
+
+        let pixels = filled_pixels(&scanline);
+        assert!(!pixels.is_empty());
+        assert_eq!(pixels, filled_pixels(&triangulated));
+
+        // The hole stays empty
+        let center = scanline.get_pixel(9, 11).unwrap();
+        assert_eq!(center.r, 0);
+    }
+
+    // Test every polygonal shape can be filled with its own color
+    #[test]
+    fn test_fill_shapes() {
+        let mut image = Image::blank(200, 200);
+
+        let triangle = Triangle::new(
+            &Point::new(10, 10),
+            &Point::new(60, 10),
+            &Point::new(10, 60),
+        )
+        .with_stroke(Color::red());
+        triangle.fill(&mut image, FillStrategy::Triangulated);
+        let pixel = image.get_pixel(20, 20).unwrap();
+        assert_eq!((pixel.r, pixel.g, pixel.b), (255, 0, 0));
+
+        let pentagon = Pentagon::new(&Point::new(140, 140), 40).with_stroke(Color::green());
+        pentagon.fill(&mut image, FillStrategy::Scanline);
+        let pixel = image.get_pixel(140, 140).unwrap();
+        assert_eq!((pixel.r, pixel.g, pixel.b), (0, 255, 0));
+
+        let rectangle = Rectangle::new(&Point::new(10, 20), &Point::new(5, 100));
+        rectangle.fill(&mut image, FillStrategy::Triangulated);
+        let pixel = image.get_pixel(10, 150).unwrap();
+        assert_eq!((pixel.r, pixel.g, pixel.b), (255, 255, 255));
+    }
+
+    // Test fills partly or fully off the image are clamped without panicking
+    #[test]
+    fn test_fill_edge_cases() {
+        let mut image = Image::blank(20, 20);
+
+        let offscreen =
+            Polygon::from_vertices(&points(&[(-50, -50), (100, -40), (30, 90)]), Color::white());
+        offscreen.fill(&mut image, FillStrategy::Scanline);
+        offscreen.fill(&mut image, FillStrategy::Triangulated);
+        assert_eq!(filled_pixels(&image).len(), 400);
+
+        let mut image = Image::blank(20, 20);
+        Polygon::new(&[]).fill(&mut image, FillStrategy::Scanline);
+        Polygon::new(&points(&[(1, 1), (5, 5), (9, 9)]))
+            .fill(&mut image, FillStrategy::Triangulated);
+        assert!(filled_pixels(&image).is_empty());
+    }
+
+    fn strategy() -> impl Strategy<Value = FillStrategy> {
+        prop_oneof![
+            Just(FillStrategy::Scanline),
+            Just(FillStrategy::Triangulated)
+        ]
+    }
+
+    proptest! {
+        // Property: fills of arbitrary (even self-intersecting) polygons stay in the bounding box
+        #[test]
+        fn prop_fill_within_bounding_box(
+            coords in prop::collection::vec((-60..60, -60..60), 0..10),
+            strategy in strategy(),
+        ) {
+            let vertices = points(&coords);
+            let polygon = Polygon::from_vertices(&vertices, Color::white());
+            let mut pixels = Recording::new();
+            polygon.fill(&mut pixels, strategy);
+
+            if let Some(area) = ClipRect::around(&vertices) {
+                prop_assert!(pixels.touched().iter().all(|&(x, y)| area.contains(x, y)));
+            } else {
+                prop_assert!(pixels.touched().is_empty());
+            }
+        }
+
+        // Property: fills with vertices anywhere in the coordinate space never panic
+        #[test]
+        fn prop_fill_extremes(
+            coords in prop::collection::vec((any::<i32>(), any::<i32>()), 0..8),
+            strategy in strategy(),
+        ) {
+            let polygon = Polygon::from_vertices(&points(&coords), Color::white());
+            let mut image = Image::blank(32, 32);
+            polygon.fill(&mut image, strategy);
+        }
+    }
+}
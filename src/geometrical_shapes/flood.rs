@@ -0,0 +1,241 @@
+use super::canvas::rgba;
+use super::{Canvas, Point};
+use raster::Color;
+
+/// Which neighbours of a pixel a fill spreads to
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Connectivity {
+    /// Left, right, up and down, stopped by any closed outline
+    #[default]
+    Four,
+    /// The diagonals too, which slips through the corners of 8-connected lines
+    Eight,
+}
+
+/// Largest difference between the channels of two pixels, alpha included
+fn distance(a: &[u8; 4], b: &[u8; 4]) -> u8 {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| a.abs_diff(*b))
+        .max()
+        .unwrap_or(0)
+}
+
+#[allow(dead_code)]
+impl Canvas {
+    /// Paints the area around `seed` whose pixels are within `tolerance` of the
+    /// seed's color on every channel, like a paint bucket
+    /// Returns how many pixels were painted
+    pub fn flood_fill(
+        &mut self,
+        seed: &Point,
+        color: &Color,
+        connectivity: Connectivity,
+        tolerance: u8,
+    ) -> usize {
+        let Some(target) = self.get_pixel(seed.x, seed.y) else {
+            return 0;
+        };
+        let target = rgba(&target);
+        self.fill_region(seed, color, connectivity, |pixel| {
+            distance(pixel, &target) <= tolerance
+        })
+    }
+
+    /// Paints the area around `seed` up to pixels of the `border` color, whatever
+    /// colors lie inside, for coloring shapes enclosed by drawn outlines
+    /// Returns how many pixels were painted, none when the seed is on the border
+    pub fn boundary_fill(
+        &mut self,
+        seed: &Point,
+        color: &Color,
+        border: &Color,
+        connectivity: Connectivity,
+    ) -> usize {
+        let border = rgba(border);
+        self.fill_region(seed, color, connectivity, |pixel| *pixel != border)
+    }
+
+    /// Scanline fill of the pixels connected to `seed` that are `inside` the region
+    /// Each popped seed is widened into a whole run of its row, then one seed is
+    /// pushed per run of the rows above and below, so the stack stays small
+    fn fill_region(
+        &mut self,
+        seed: &Point,
+        color: &Color,
+        connectivity: Connectivity,
+        inside: impl Fn(&[u8; 4]) -> bool,
+    ) -> usize {
+        let (width, height) = (self.width(), self.height());
+        if seed.x < 0 || seed.y < 0 || seed.x >= width || seed.y >= height {
+            return 0;
+        }
+        let value = rgba(color);
+        let reach = match connectivity {
+            Connectivity::Four => 0,
+            Connectivity::Eight => 1,
+        };
+
+        // Painted pixels may still look inside, so they are tracked separately
+        let mut visited = vec![false; width as usize * height as usize];
+        let pixels = self.pixels_mut();
+        let index = |x: i32, y: i32| (y * width + x) as usize;
+        let open = |pixels: &[[u8; 4]], visited: &[bool], x: i32, y: i32| {
+            !visited[index(x, y)] && inside(&pixels[index(x, y)])
+        };
+
+        let mut painted = 0;
+        let mut stack = vec![(seed.x, seed.y)];
+        while let Some((x, y)) = stack.pop() {
+            if !open(pixels, &visited, x, y) {
+                continue;
+            }
+
+            let (mut left, mut right) = (x, x);
+            while left > 0 && open(pixels, &visited, left - 1, y) {
+                left -= 1;
+            }
+            while right < width - 1 && open(pixels, &visited, right + 1, y) {
+                right += 1;
+            }
+            visited[index(left, y)..=index(right, y)].fill(true);
+            pixels[index(left, y)..=index(right, y)].fill(value);
+            painted += (right - left + 1) as usize;
+
+            let (from, to) = ((left - reach).max(0), (right + reach).min(width - 1));
+            for row in [y - 1, y + 1] {
+                if row < 0 || row >= height {
+                    continue;
+                }
+                let mut column = from;
+                while column <= to {
+                    if open(pixels, &visited, column, row) {
+                        stack.push((column, row));
+                        while column <= to && open(pixels, &visited, column, row) {
+                            column += 1;
+                        }
+                    } else {
+                        column += 1;
+                    }
+                }
+            }
+        }
+        painted
+    }
+}
+
+// Unit tests for flood and boundary fills
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometrical_shapes::{Circle, ClipRect, Displayable, Drawable};
+
+    /// Pixels of the canvas matching `color`
+    fn count(canvas: &Canvas, color: &Color) -> usize {
+        let value = rgba(color);
+        (0..canvas.height())
+            .flat_map(|y| canvas.row(y).iter())
+            .filter(|pixel| **pixel == value)
+            .count()
+    }
+
+    // Test diagonal neighbours are only reached when 8-connected
+    #[test]
+    fn test_flood_connectivity() {
+        // Two black squares touching at a single corner, on white
+        let mut canvas = Canvas::filled(6, 6, &Color::white());
+        canvas.fill_rect(&ClipRect::new(0, 0, 2, 2), &Color::black());
+        canvas.fill_rect(&ClipRect::new(3, 3, 5, 5), &Color::black());
+
+        let mut four = canvas.clone();
+        let painted = four.flood_fill(&Point::new(1, 1), &Color::red(), Connectivity::Four, 0);
+        assert_eq!(painted, 9);
+        assert_eq!(four.get_pixel(4, 4).unwrap().r, 0);
+
+        let painted = canvas.flood_fill(&Point::new(1, 1), &Color::red(), Connectivity::Eight, 0);
+        assert_eq!(painted, 18);
+        assert_eq!(canvas.get_pixel(4, 4).unwrap().r, 255);
+        assert_eq!(count(&canvas, &Color::white()), 18);
+    }
+
+    // Test the tolerance is measured against the seed's color
+    #[test]
+    fn test_flood_tolerance() {
+        let mut canvas = Canvas::new(5, 1);
+        for x in 0..5 {
+            let level = x as u8 * 10;
+            canvas.display(x, 0, Color::rgb(level, level, level));
+        }
+
+        let mut strict = canvas.clone();
+        assert_eq!(
+            strict.flood_fill(&Point::new(0, 0), &Color::blue(), Connectivity::Four, 0),
+            1
+        );
+        assert_eq!(
+            canvas.flood_fill(&Point::new(0, 0), &Color::blue(), Connectivity::Four, 25),
+            3
+        );
+        assert_eq!(canvas.get_pixel(3, 0).unwrap().r, 30);
+
+        // Refilling with a color the region already matches stops
+        assert_eq!(
+            canvas.flood_fill(&Point::new(0, 0), &Color::blue(), Connectivity::Four, 255),
+            5
+        );
+        assert_eq!(
+            canvas.flood_fill(&Point::new(9, 0), &Color::red(), Connectivity::Four, 0),
+            0
+        );
+    }
+
+    // Test boundary fills color the inside of an outline, whatever is drawn there
+    #[test]
+    fn test_boundary_fill() {
+        let mut canvas = Canvas::new(40, 40);
+        Circle::from_center(&Point::new(20, 20), 15).draw(&mut canvas);
+        canvas.display(20, 20, Color::green());
+
+        let outline = count(&canvas, &Color::white());
+        let painted = canvas.boundary_fill(
+            &Point::new(18, 18),
+            &Color::red(),
+            &Color::white(),
+            Connectivity::Four,
+        );
+        // Between the areas of circles of radius 13 and 15 around the outline
+        assert!(painted > 530 && painted < 710, "{painted}");
+        assert_eq!(count(&canvas, &Color::red()), painted);
+        assert_eq!(count(&canvas, &Color::white()), outline);
+        assert_eq!(canvas.get_pixel(20, 20).unwrap().g, 0);
+        assert_eq!(canvas.get_pixel(0, 0).unwrap().r, 0);
+
+        // Seeds on the border paint nothing
+        let on_border = Point::new(35, 20);
+        assert_eq!(canvas.get_pixel(35, 20).unwrap().b, 255);
+        assert_eq!(
+            canvas.boundary_fill(
+                &on_border,
+                &Color::blue(),
+                &Color::white(),
+                Connectivity::Four
+            ),
+            0
+        );
+    }
+
+    // Test whole canvases are filled without overflowing the stack
+    #[test]
+    fn test_flood_large() {
+        let mut canvas = Canvas::new(1000, 1000);
+        let painted = canvas.flood_fill(
+            &Point::new(500, 500),
+            &Color::white(),
+            Connectivity::Eight,
+            0,
+        );
+        assert_eq!(painted, 1_000_000);
+        assert!(canvas.as_bytes().iter().all(|&byte| byte == 255));
+    }
+}
@@ -0,0 +1,120 @@
+use super::Drawable;
+use crate::diff::compare;
+use raster::Image;
+use std::path::{Path, PathBuf};
+
+/// How far a rendering may drift from its reference before failing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Tolerance {
+    /// Largest difference allowed in any single channel of a pixel
+    pub channel: u8,
+    /// Number of pixels allowed to exceed the channel tolerance
+    pub pixels: usize,
+}
+
+impl Tolerance {
+    /// Every pixel must match exactly
+    pub const EXACT: Tolerance = Tolerance {
+        channel: 0,
+        pixels: 0,
+    };
+}
+
+/// Draws `shapes` in order and in their own colors onto a blank black image
+/// Give them explicit colors, so the snapshot catches color regressions too
+pub fn render(width: i32, height: i32, shapes: &[&dyn Drawable]) -> Image {
+    let mut image = Image::blank(width, height);
+    for shape in shapes {
+        shape.draw(&mut image);
+    }
+    image
+}
+
+fn reference_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+        .join(format!("{name}.png"))
+}
+
+fn failure_path(name: &str, suffix: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("target")
+        .join("golden")
+        .join(format!("{name}.{suffix}.png"))
+}
+
+fn save(image: &Image, path: &Path) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    raster::save(image, path.to_str().unwrap()).unwrap();
+}
+
+/// Compares `image` against the checked-in reference `tests/golden/<name>.png`
+/// On failure, writes the actual rendering and a diff image to `target/golden`
+/// With `BLESS=1` set, overwrites the reference instead of comparing
+pub fn assert_golden(name: &str, image: &Image, tolerance: Tolerance) {
+    let reference = reference_path(name);
+
+    if std::env::var_os("BLESS").is_some_and(|v| !v.is_empty() && v != "0") {
+        save(image, &reference);
+        return;
+    }
+
+    let Ok(expected) = raster::open(reference.to_str().unwrap()) else {
+        panic!(
+            "missing reference {}, run `make bless` to create it",
+            reference.display()
+        );
+    };
+
+    let actual = failure_path(name, "actual");
+    if (expected.width, expected.height) != (image.width, image.height) {
+        save(image, &actual);
+        panic!(
+            "{name}: rendered {}x{} but reference is {}x{}, actual image written to {}",
+            image.width,
+            image.height,
+            expected.width,
+            expected.height,
+            actual.display()
+        );
+    }
+
+    let diff = compare(&expected, image, tolerance.channel).unwrap();
+    if diff.differing > tolerance.pixels {
+        let diff_path = failure_path(name, "diff");
+        save(image, &actual);
+        save(&diff.image, &diff_path);
+        panic!(
+            "{name}: {} pixels differ by more than {} (allowed {}), \
+             actual image written to {} and diff to {}\n{diff}",
+            diff.differing,
+            tolerance.channel,
+            tolerance.pixels,
+            actual.display(),
+            diff_path.display()
+        );
+    }
+}
+
+// Unit tests for the golden-image harness itself
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometrical_shapes::{Line, Point};
+    use raster::Color;
+
+    // Test rendering keeps each shape's own color, later shapes on top
+    #[test]
+    fn test_render_colors() {
+        let red = Line::from_points(&Point::new(0, 5), &Point::new(9, 5), 1, Color::red());
+        let blue = Line::from_points(&Point::new(5, 0), &Point::new(5, 9), 1, Color::blue());
+        let image = render(10, 10, &[&red, &blue]);
+
+        let pixel = image.get_pixel(2, 5).unwrap();
+        assert_eq!((pixel.r, pixel.g, pixel.b), (255, 0, 0));
+        let pixel = image.get_pixel(5, 5).unwrap();
+        assert_eq!((pixel.r, pixel.g, pixel.b), (0, 0, 255));
+        assert_eq!(image.get_pixel(0, 0).unwrap().r, 0);
+    }
+}
@@ -1,7 +1,8 @@
 // line.rs
-use super::{Displayable, Drawable, Point};
+use super::stroke::StrokeMask;
+use super::{Animatable, ClipRect, Displayable, Drawable, LineCap, Painted, Point, Style, Styled};
 use rand::Rng;
-use raster::{Color, Image};
+use raster::Color;
 
 /// Implementation of PartialEq for Point to enable equality comparisons
 impl PartialEq for Point {
@@ -10,84 +11,88 @@ impl PartialEq for Point {
     }
 }
 
-/// Draws a line with specified color and thickness using Bresenham's algorithm
-fn draw_line_with_color(
+/// Rasterizes a line of the given thickness using Bresenham's algorithm
+/// Passes every covered pixel to `plot` exactly once, walking only the steps
+/// that can reach `bounds`, if there are any
+pub(super) fn rasterize_line(
     start: &Point,
     end: &Point,
     thickness: i32,
-    image: &mut Image,
-    color: &Color,
+    bounds: Option<ClipRect>,
+    plot: &mut dyn FnMut(i32, i32),
 ) {
-    let dx = (end.x - start.x).abs();
-    let dy = (end.y - start.y).abs();
+    let (x0, y0) = (start.x as i64, start.y as i64);
+    let dx = (end.x as i64 - x0).abs();
+    let dy = (end.y as i64 - y0).abs();
     let sx = if start.x < end.x { 1 } else { -1 };
     let sy = if start.y < end.y { 1 } else { -1 };
-    let err = dx - dy;
+    let steps = dx.max(dy);
+
+    // Pixel reached after `i` steps along the major axis,
+    // the minor axis is rounded to the nearest pixel like Bresenham's error term
+    let at = |i: i64| -> (i64, i64) {
+        let minor = |d_minor: i64, d_major: i64| {
+            ((2 * i as i128 * d_minor as i128 + d_major as i128) / (2 * d_major as i128)) as i64
+        };
+        if dx > dy {
+            (x0 + sx * i, y0 + sy * minor(dy, dx))
+        } else if dy > 0 {
+            (x0 + sx * minor(dx, dy), y0 + sy * i)
+        } else {
+            (x0, y0)
+        }
+    };
+
+    let (mut first, mut last) = (0, steps);
+    if let Some(bounds) = bounds {
+        // Thick passes spread up to half the thickness off the center line
+        let reach = bounds.expand(thickness.saturating_abs() / 2 + 1);
+        match reach.clip_segment((x0 as f64, y0 as f64), (end.x as f64, end.y as f64)) {
+            Some((t0, t1)) => {
+                first = ((t0 * steps as f64).floor() as i64 - 1).max(0);
+                last = ((t1 * steps as f64).ceil() as i64 + 1).min(steps);
+            }
+            None => return,
+        }
+    }
 
     for t in 0..thickness {
-        let offset = t - thickness / 2;
-        let mut x = start.x;
-        let mut y = start.y;
-        let mut local_err = err;
-        loop {
-            if dx > dy {
-                image.display(x, y + offset, color.clone());
+        let offset = (t - thickness / 2) as i64;
+        for i in first..=last {
+            let (x, y) = at(i);
+            let (x, y) = if dx > dy {
+                (x, y + offset)
             } else {
-                image.display(x + offset, y, color.clone());
-            }
-            if x == end.x && y == end.y {
-                break;
-            }
-            let e2 = 2 * local_err;
-            if e2 > -dy {
-                local_err -= dy;
-                x += sx;
-            }
-            if e2 < dx {
-                local_err += dx;
-                y += sy;
+                (x + offset, y)
+            };
+            if let (Ok(x), Ok(y)) = (i32::try_from(x), i32::try_from(y)) {
+                plot(x, y);
             }
         }
     }
 }
 
-/// Represents a line segment with start/end points, thickness and color
+/// Represents a line segment with start/end points and a style
+#[derive(Clone)]
 pub struct Line {
     start: Point,
     end: Point,
-    thickness: i32,
-    color: Color,
+    style: Style,
 }
 
 #[allow(dead_code)]
 impl Line {
-    /// Creates a new line between two points with random thickness and color
+    /// Creates a new line between two points, 2px thick in white
     pub fn new(p1: &Point, p2: &Point) -> Self {
-        let mut rng = rand::thread_rng();
-        Line {
-            start: p1.clone(),
-            end: p2.clone(),
-            thickness: rng.gen_range(2..5),
-            color: Color::rgb(
-                rng.gen_range(50..200),
-                rng.gen_range(50..200),
-                rng.gen_range(50..200),
-            ),
-        }
+        Line::from_points(p1, p2, 2, Color::white())
     }
 
     /// Creates a random line within specified bounds
     pub fn random(width: i32, height: i32) -> Self {
-        let mut rng = rand::thread_rng();
         Line {
             start: Point::random(width, height),
             end: Point::random(width, height),
-            thickness: rng.gen_range(2..5),
-            color: Color::rgb(
-                rng.gen_range(50..200),
-                rng.gen_range(50..200),
-                rng.gen_range(50..200),
-            ),
+            style: Style::random(50..200).width(rand::thread_rng().gen_range(2..5)),
         }
     }
 
@@ -96,21 +101,79 @@ impl Line {
         Line {
             start: p1.clone(),
             end: p2.clone(),
-            thickness,
-            color,
+            style: Style::new().stroke(color).width(thickness),
+        }
+    }
+}
+
+impl Styled for Line {
+    fn style(&self) -> &Style {
+        &self.style
+    }
+
+    fn style_mut(&mut self) -> &mut Style {
+        &mut self.style
+    }
+}
+
+impl Animatable for Line {
+    /// Moves both end points and blends the style
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        Line {
+            start: self.start.interpolate(&to.start, t),
+            end: self.end.interpolate(&to.end, t),
+            style: self.style.interpolate(&to.style, t),
         }
     }
 }
 
 impl Drawable for Line {
     /// Draws the line on the specified image
-    fn draw(&self, image: &mut Image) {
-        draw_line_with_color(&self.start, &self.end, self.thickness, image, &self.color);
+    /// A single solid line without caps never covers a pixel twice,
+    /// so it skips the stroke mask
+    fn draw(&self, image: &mut dyn Displayable) {
+        if !self.is_visible_on(image) {
+            return;
+        }
+
+        if self.style.dash.is_some() || self.style.cap != LineCap::Butt {
+            let mut mask = StrokeMask::new(image).styled(&self.style);
+            mask.add_path(
+                &[self.start.clone(), self.end.clone()],
+                self.style.width,
+                false,
+            );
+            self.style.paint_stroke(mask, image);
+            return;
+        }
+
+        let Some(paint) = &self.style.stroke else {
+            return;
+        };
+        let color = self.color();
+        let bounds = image.bounds();
+        let mut target = Painted::new(image, paint).with_opacity(self.style.opacity);
+        rasterize_line(
+            &self.start,
+            &self.end,
+            self.style.width,
+            bounds,
+            &mut |x, y| target.display(x, y, color.clone()),
+        );
     }
 
-    /// Returns the color of the line
+    /// Returns the color of the line's style
     fn color(&self) -> Color {
-        self.color.clone()
+        self.style.color()
+    }
+
+    /// Covers both endpoints, widened by half the thickness and the caps
+    fn bounding_box(&self) -> Option<ClipRect> {
+        let reach = match self.style.cap {
+            LineCap::Butt => self.style.width.saturating_abs() / 2,
+            _ => self.style.width.saturating_abs(),
+        };
+        ClipRect::around([&self.start, &self.end]).map(|area| area.expand(reach))
     }
 }
 
@@ -118,7 +181,11 @@ impl Drawable for Line {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::geometrical_shapes::Recording;
+    use crate::geometrical_shapes::golden::{self, Tolerance};
+    use proptest::prelude::*;
     use raster::Image;
+    use std::collections::HashSet;
 
     // Test basic line creation with expected properties
     #[test]
@@ -129,10 +196,9 @@ mod tests {
 
         assert_eq!(line.start, p1);
         assert_eq!(line.end, p2);
-        assert!(line.thickness >= 2 && line.thickness < 5);
-        assert!(line.color.r >= 50 && line.color.r < 200);
-        assert!(line.color.g >= 50 && line.color.g < 200);
-        assert!(line.color.b >= 50 && line.color.b < 200);
+        assert_eq!(line.style.width, 2);
+        let color = line.color();
+        assert_eq!((color.r, color.g, color.b), (255, 255, 255));
     }
 
     // Test random line generation stays within bounds
@@ -146,10 +212,10 @@ mod tests {
         assert!(line.start.y >= 0 && line.start.y < height);
         assert!(line.end.x >= 0 && line.end.x < width);
         assert!(line.end.y >= 0 && line.end.y < height);
-        assert!(line.thickness >= 2 && line.thickness < 5);
-        assert!(line.color.r >= 50 && line.color.r < 200);
-        assert!(line.color.g >= 50 && line.color.g < 200);
-        assert!(line.color.b >= 50 && line.color.b < 200);
+        assert!(line.style.width >= 2 && line.style.width < 5);
+        assert!(line.color().r >= 50 && line.color().r < 200);
+        assert!(line.color().g >= 50 && line.color().g < 200);
+        assert!(line.color().b >= 50 && line.color().b < 200);
     }
 
     // Test line creation with explicit parameters
@@ -163,10 +229,10 @@ mod tests {
 
         assert_eq!(line.start, p1);
         assert_eq!(line.end, p2);
-        assert_eq!(line.thickness, thickness);
-        assert_eq!(line.color.r, color.r);
-        assert_eq!(line.color.g, color.g);
-        assert_eq!(line.color.b, color.b);
+        assert_eq!(line.style.width, thickness);
+        assert_eq!(line.color().r, color.r);
+        assert_eq!(line.color().g, color.g);
+        assert_eq!(line.color().b, color.b);
     }
 
     // Test color getter returns correct color
@@ -203,6 +269,12 @@ mod tests {
             assert_eq!(pixel.g, color.g);
             assert_eq!(pixel.b, color.b);
         }
+
+        // Nothing beyond the endpoints is written, and nothing twice
+        let mut recording = Recording::new();
+        line.draw(&mut recording);
+        assert_eq!(recording.touched(), (5..=15).map(|x| (x, 10)).collect());
+        assert_eq!(recording.overdraw(), 0);
     }
 
     // Test vertical line drawing
@@ -292,4 +364,71 @@ mod tests {
         assert_eq!(pixel2.g, color.g);
         assert_eq!(pixel2.b, color.b);
     }
+
+    // Test steep, shallow and thick lines against their golden image
+    #[test]
+    fn test_line_golden() {
+        let shallow = Line::from_points(&Point::new(5, 10), &Point::new(90, 40), 1, Color::red());
+        let steep = Line::from_points(&Point::new(20, 95), &Point::new(35, 5), 2, Color::green());
+        let thick = Line::from_points(&Point::new(10, 80), &Point::new(95, 60), 5, Color::blue());
+
+        let image = golden::render(100, 100, &[&shallow, &steep, &thick]);
+        golden::assert_golden("line", &image, Tolerance::EXACT);
+    }
+
+    /// Checks every pixel can be reached from `start` through its 8 neighbours
+    fn is_8_connected(pixels: &HashSet<(i32, i32)>, start: (i32, i32)) -> bool {
+        let mut seen = HashSet::from([start]);
+        let mut stack = vec![start];
+        while let Some((x, y)) = stack.pop() {
+            for dx in -1..=1 {
+                for dy in -1..=1 {
+                    let (Some(nx), Some(ny)) = (x.checked_add(dx), y.checked_add(dy)) else {
+                        continue;
+                    };
+                    if pixels.contains(&(nx, ny)) && seen.insert((nx, ny)) {
+                        stack.push((nx, ny));
+                    }
+                }
+            }
+        }
+        seen.len() == pixels.len()
+    }
+
+    proptest! {
+        // Property: lines touch both endpoints and form one 8-connected run
+        #[test]
+        fn prop_line_endpoints_connected(
+            x0 in -200..200, y0 in -200..200,
+            x1 in -200..200, y1 in -200..200,
+            thickness in 1..6,
+        ) {
+            let line = Line::from_points(&Point::new(x0, y0), &Point::new(x1, y1), thickness, Color::white());
+            let mut pixels = Recording::new();
+            line.draw(&mut pixels);
+
+            let touched = pixels.touched();
+            prop_assert!(touched.contains(&(x0, y0)));
+            prop_assert!(touched.contains(&(x1, y1)));
+            prop_assert!(is_8_connected(&touched, (x0, y0)));
+        }
+
+        // Property: the same holds at the edge of the coordinate space
+        #[test]
+        fn prop_line_extremes(
+            x0 in (i32::MAX - 300)..=i32::MAX, y0 in i32::MIN..=(i32::MIN + 300),
+            dx in 0..300, dy in 0..300,
+            thickness in 1..6,
+        ) {
+            let (x1, y1) = (x0 - dx, y0 + dy);
+            let line = Line::from_points(&Point::new(x0, y0), &Point::new(x1, y1), thickness, Color::white());
+            let mut pixels = Recording::new();
+            line.draw(&mut pixels);
+
+            let touched = pixels.touched();
+            prop_assert!(touched.contains(&(x0, y0)));
+            prop_assert!(touched.contains(&(x1, y1)));
+            prop_assert!(is_8_connected(&touched, (x0, y0)));
+        }
+    }
 }
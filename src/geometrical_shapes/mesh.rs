@@ -0,0 +1,370 @@
+use super::{ClipRect, Line, Point, Polygon, Triangle};
+use std::collections::BTreeMap;
+
+/// Cross product of vectors `o -> a` and `o -> b`
+/// Positive when `a -> b` turns counter-clockwise around `o`
+/// Widened so points anywhere in the coordinate space cannot overflow
+fn cross(o: &Point, a: &Point, b: &Point) -> i128 {
+    (a.x as i128 - o.x as i128) * (b.y as i128 - o.y as i128)
+        - (a.y as i128 - o.y as i128) * (b.x as i128 - o.x as i128)
+}
+
+/// Returns the input points sorted by (x, y) with duplicates removed
+fn sorted_unique(points: &[Point]) -> Vec<Point> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by_key(|p| (p.x, p.y));
+    sorted.dedup();
+    sorted
+}
+
+/// Computes the convex hull vertices using Andrew's monotone chain
+/// Returns the hull in counter-clockwise order without collinear points
+pub(super) fn hull_points(points: &[Point]) -> Vec<Point> {
+    let sorted = sorted_unique(points);
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    let mut lower: Vec<Point> = Vec::new();
+    for p in &sorted {
+        while lower.len() >= 2 && cross(&lower[lower.len() - 2], &lower[lower.len() - 1], p) <= 0 {
+            lower.pop();
+        }
+        lower.push(p.clone());
+    }
+
+    let mut upper: Vec<Point> = Vec::new();
+    for p in sorted.iter().rev() {
+        while upper.len() >= 2 && cross(&upper[upper.len() - 2], &upper[upper.len() - 1], p) <= 0 {
+            upper.pop();
+        }
+        upper.push(p.clone());
+    }
+
+    // Last point of each chain is the first point of the other one
+    lower.pop();
+    upper.pop();
+    lower.extend(upper);
+    lower
+}
+
+/// Builds the convex hull of a point set as a drawable polygon
+#[allow(dead_code)]
+pub fn convex_hull(points: &[Point]) -> Polygon {
+    Polygon::new(&hull_points(points))
+}
+
+/// Circumcircle of a triangle as (center x, center y, squared radius)
+/// Returns `None` for degenerate (collinear) triangles
+fn circumcircle(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> Option<(f64, f64, f64)> {
+    let d = 2.0 * (a.0 * (b.1 - c.1) + b.0 * (c.1 - a.1) + c.0 * (a.1 - b.1));
+    if d.abs() < f64::EPSILON {
+        return None;
+    }
+
+    let a2 = a.0 * a.0 + a.1 * a.1;
+    let b2 = b.0 * b.0 + b.1 * b.1;
+    let c2 = c.0 * c.0 + c.1 * c.1;
+    let ux = (a2 * (b.1 - c.1) + b2 * (c.1 - a.1) + c2 * (a.1 - b.1)) / d;
+    let uy = (a2 * (c.0 - b.0) + b2 * (a.0 - c.0) + c2 * (b.0 - a.0)) / d;
+    let r2 = (a.0 - ux).powi(2) + (a.1 - uy).powi(2);
+
+    Some((ux, uy, r2))
+}
+
+/// Triangulates a point set using the Bowyer-Watson algorithm
+/// Returns the unique input points and triangles as indices into them
+fn delaunay_indices(points: &[Point]) -> (Vec<Point>, Vec<[usize; 3]>) {
+    let unique = sorted_unique(points);
+    let n = unique.len();
+    if n < 3 {
+        return (unique, Vec::new());
+    }
+
+    let mut coords: Vec<(f64, f64)> = unique.iter().map(|p| (p.x as f64, p.y as f64)).collect();
+
+    // Super triangle large enough to contain every input point
+    let (min_x, max_x) = coords.iter().fold((f64::MAX, f64::MIN), |(lo, hi), p| {
+        (lo.min(p.0), hi.max(p.0))
+    });
+    let (min_y, max_y) = coords.iter().fold((f64::MAX, f64::MIN), |(lo, hi), p| {
+        (lo.min(p.1), hi.max(p.1))
+    });
+    let span = (max_x - min_x).max(max_y - min_y).max(1.0) * 20.0;
+    let mid_x = (min_x + max_x) / 2.0;
+    let mid_y = (min_y + max_y) / 2.0;
+    coords.push((mid_x - span, mid_y - span));
+    coords.push((mid_x + span, mid_y - span));
+    coords.push((mid_x, mid_y + span));
+
+    let mut triangles: Vec<([usize; 3], (f64, f64, f64))> = Vec::new();
+    if let Some(circle) = circumcircle(coords[n], coords[n + 1], coords[n + 2]) {
+        triangles.push(([n, n + 1, n + 2], circle));
+    }
+
+    for i in 0..n {
+        let (px, py) = coords[i];
+
+        let (bad, good): (Vec<_>, Vec<_>) = triangles
+            .into_iter()
+            .partition(|(_, (cx, cy, r2))| (px - cx).powi(2) + (py - cy).powi(2) < *r2);
+        triangles = good;
+
+        // Edges of the cavity appear in exactly one bad triangle
+        let mut edge_count: BTreeMap<(usize, usize), u32> = BTreeMap::new();
+        for (tri, _) in &bad {
+            for k in 0..3 {
+                let (a, b) = (tri[k], tri[(k + 1) % 3]);
+                *edge_count.entry((a.min(b), a.max(b))).or_insert(0) += 1;
+            }
+        }
+
+        for ((a, b), count) in edge_count {
+            if count != 1 {
+                continue;
+            }
+            if let Some(circle) = circumcircle(coords[a], coords[b], coords[i]) {
+                triangles.push(([a, b, i], circle));
+            }
+        }
+    }
+
+    let tris = triangles
+        .into_iter()
+        .map(|(tri, _)| tri)
+        .filter(|tri| tri.iter().all(|&v| v < n))
+        .collect();
+
+    (unique, tris)
+}
+
+/// Builds the Delaunay triangulation of a point set as drawable triangles
+#[allow(dead_code)]
+pub fn delaunay(points: &[Point]) -> Vec<Triangle> {
+    let (vertices, tris) = delaunay_indices(points);
+
+    tris.iter()
+        .map(|[a, b, c]| Triangle::new(&vertices[*a], &vertices[*b], &vertices[*c]))
+        .collect()
+}
+
+/// Computes Voronoi cell edges as segments clipped to the given bounds
+/// Derived from the Delaunay dual: shared triangle edges connect circumcenters,
+/// hull edges emit a ray from the circumcenter away from the triangle
+fn voronoi_segments(points: &[Point], width: i32, height: i32) -> Vec<(Point, Point)> {
+    let (vertices, tris) = delaunay_indices(points);
+    let coords: Vec<(f64, f64)> = vertices.iter().map(|p| (p.x as f64, p.y as f64)).collect();
+    let far = (width as f64 + height as f64) * 4.0;
+    let bounds = ClipRect::from_size(width, height);
+
+    let centers: Vec<(f64, f64)> = tris
+        .iter()
+        .map(|t| {
+            let (cx, cy, _) =
+                circumcircle(coords[t[0]], coords[t[1]], coords[t[2]]).unwrap_or_default();
+            (cx, cy)
+        })
+        .collect();
+
+    // Map every undirected edge to the triangles (and opposite vertex) using it,
+    // ordered so the segments come out the same on every run
+    let mut edges: BTreeMap<(usize, usize), Vec<(usize, usize)>> = BTreeMap::new();
+    for (ti, t) in tris.iter().enumerate() {
+        for k in 0..3 {
+            let (a, b, opposite) = (t[k], t[(k + 1) % 3], t[(k + 2) % 3]);
+            edges
+                .entry((a.min(b), a.max(b)))
+                .or_default()
+                .push((ti, opposite));
+        }
+    }
+
+    let mut segments = Vec::new();
+    for ((a, b), users) in edges {
+        let raw = match users.as_slice() {
+            [(t0, _), (t1, _)] => (centers[*t0], centers[*t1]),
+            [(t0, opposite)] => {
+                let (ax, ay) = coords[a];
+                let (bx, by) = coords[b];
+                let (ox, oy) = coords[*opposite];
+                let (mut nx, mut ny) = (by - ay, ax - bx);
+                // Point the normal away from the vertex opposite the hull edge
+                if nx * (ox - ax) + ny * (oy - ay) > 0.0 {
+                    nx = -nx;
+                    ny = -ny;
+                }
+                let len = (nx * nx + ny * ny).sqrt();
+                let start = centers[*t0];
+                (start, (start.0 + nx / len * far, start.1 + ny / len * far))
+            }
+            _ => continue,
+        };
+
+        if let Some((t0, t1)) = bounds.clip_segment(raw.0, raw.1) {
+            let at = |t: f64| {
+                Point::new(
+                    (raw.0.0 + t * (raw.1.0 - raw.0.0)).round() as i32,
+                    (raw.0.1 + t * (raw.1.1 - raw.0.1)).round() as i32,
+                )
+            };
+            segments.push((at(t0), at(t1)));
+        }
+    }
+
+    segments
+}
+
+/// Builds the Voronoi diagram of a point set as drawable lines
+/// Unbounded cell edges are clipped to a `width` x `height` area
+#[allow(dead_code)]
+pub fn voronoi(points: &[Point], width: i32, height: i32) -> Vec<Line> {
+    voronoi_segments(points, width, height)
+        .iter()
+        .map(|(start, end)| Line::new(start, end))
+        .collect()
+}
+
+// Unit tests for point set meshes
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square_with_interior() -> Vec<Point> {
+        vec![
+            Point::new(0, 0),
+            Point::new(100, 0),
+            Point::new(100, 100),
+            Point::new(0, 100),
+            Point::new(50, 50),
+            Point::new(20, 70),
+            Point::new(50, 0),
+        ]
+    }
+
+    // Test hull keeps only the corners and skips interior/collinear points
+    #[test]
+    fn test_hull_points_square() {
+        let hull = hull_points(&square_with_interior());
+
+        assert_eq!(
+            hull,
+            vec![
+                Point::new(0, 0),
+                Point::new(100, 0),
+                Point::new(100, 100),
+                Point::new(0, 100),
+            ]
+        );
+    }
+
+    // Test every random point lies inside or on the hull
+    #[test]
+    fn test_convex_hull_contains_points() {
+        let points: Vec<Point> = (0..200).map(|_| Point::random(500, 500)).collect();
+        let hull = convex_hull(&points);
+        let vertices = hull.vertices();
+
+        for p in &points {
+            for i in 0..vertices.len() {
+                let a = &vertices[i];
+                let b = &vertices[(i + 1) % vertices.len()];
+                assert!(cross(a, b, p) >= 0);
+            }
+        }
+    }
+
+    // Test hull of fewer than three points returns them unchanged
+    #[test]
+    fn test_hull_points_edge_cases() {
+        assert!(hull_points(&[]).is_empty());
+        assert_eq!(hull_points(&[Point::new(1, 1), Point::new(1, 1)]).len(), 1);
+        assert_eq!(hull_points(&[Point::new(0, 0), Point::new(5, 5)]).len(), 2);
+    }
+
+    // Test a square is split into exactly two triangles
+    #[test]
+    fn test_delaunay_square() {
+        let points = [
+            Point::new(0, 0),
+            Point::new(10, 0),
+            Point::new(10, 12),
+            Point::new(0, 12),
+        ];
+        let (_, tris) = delaunay_indices(&points);
+        assert_eq!(tris.len(), 2);
+        assert_eq!(delaunay(&points).len(), 2);
+    }
+
+    // Test no input point falls strictly inside any triangle's circumcircle
+    #[test]
+    fn test_delaunay_empty_circumcircle() {
+        let points: Vec<Point> = (0..60).map(|_| Point::random(400, 400)).collect();
+        let (vertices, tris) = delaunay_indices(&points);
+        assert!(!tris.is_empty());
+
+        for t in &tris {
+            let c = |i: usize| (vertices[i].x as f64, vertices[i].y as f64);
+            let (cx, cy, r2) = circumcircle(c(t[0]), c(t[1]), c(t[2])).unwrap();
+            for p in &vertices {
+                let d2 = (p.x as f64 - cx).powi(2) + (p.y as f64 - cy).powi(2);
+                assert!(d2 >= r2 - 1e-6 * r2.max(1.0));
+            }
+        }
+    }
+
+    // Test Delaunay of collinear or too few points produces nothing
+    #[test]
+    fn test_delaunay_edge_cases() {
+        assert!(delaunay(&[]).is_empty());
+        assert!(delaunay(&[Point::new(0, 0), Point::new(1, 1)]).is_empty());
+        assert!(delaunay(&[Point::new(0, 0), Point::new(5, 5), Point::new(10, 10)]).is_empty());
+    }
+
+    // Test Voronoi edges stay within the requested bounds
+    #[test]
+    fn test_voronoi_bounds() {
+        let points: Vec<Point> = (0..40).map(|_| Point::random(300, 200)).collect();
+        let segments = voronoi_segments(&points, 300, 200);
+        assert!(!segments.is_empty());
+
+        for (start, end) in &segments {
+            for p in [start, end] {
+                assert!(p.x >= 0 && p.x < 300);
+                assert!(p.y >= 0 && p.y < 200);
+            }
+        }
+        assert_eq!(voronoi(&points, 300, 200).len(), segments.len());
+    }
+
+    // Test Voronoi edges come out in the same order on every call
+    #[test]
+    fn test_voronoi_deterministic() {
+        let points: Vec<Point> = (0..40).map(|_| Point::random(300, 200)).collect();
+        let first = voronoi_segments(&points, 300, 200);
+        for _ in 0..5 {
+            assert_eq!(voronoi_segments(&points, 300, 200), first);
+        }
+    }
+
+    // Test Voronoi of a symmetric pair is the perpendicular bisector
+    #[test]
+    fn test_voronoi_square_cells() {
+        let points = [
+            Point::new(25, 25),
+            Point::new(75, 25),
+            Point::new(75, 75),
+            Point::new(25, 75),
+        ];
+        let segments = voronoi_segments(&points, 100, 100);
+
+        // Every cell boundary lies on the x = 50 or y = 50 axes
+        for (start, end) in &segments {
+            assert!(
+                (start.x == 50 && end.x == 50) || (start.y == 50 && end.y == 50),
+                "unexpected segment {:?} -> {:?}",
+                start,
+                end
+            );
+        }
+    }
+}
@@ -18,34 +18,154 @@ mod triangle;
 /// Circle primitive module
 mod circle;
 
+/// Owned in-memory drawing surface module
+mod canvas;
+
+/// Color spaces, parsing and palettes module
+mod color;
+
+/// Scientific colormaps module (viridis, magma, plasma, cividis, turbo)
+mod colormap;
+
 /// Pentagon primitive module
 mod pentagon;
 
 /// 3D Cube primitive module (isometric projection)
 mod cubes;
 
+/// Arbitrary closed polygon module
+mod polygon;
+
+/// Point set meshes module (convex hull, Delaunay, Voronoi)
+mod mesh;
+
+/// Ear-clipping polygon triangulation module
+mod triangulation;
+
+/// Polygon interior filling module
+mod fill;
+
+/// Flood and boundary fill module (paint bucket on a canvas)
+mod flood;
+
+/// Polygon boolean operations module (union, intersection, difference, xor)
+mod boolean;
+
+/// Clipping regions module (clip rectangles, masks and the clip stack)
+mod clip;
+
+/// Paint module (flat colors and linear, radial and conic gradients)
+mod paint;
+
+/// Repeating fill pattern module (hatches, dots, checkerboards, tiles)
+mod pattern;
+
+/// Dash pattern module for dashed and dotted strokes
+mod dash;
+
+/// Overdraw-free outline stroking module
+mod stroke;
+
+/// Shape styling module (stroke, width, dash, caps, joins, fill, opacity)
+mod style;
+
+/// Supersampling anti-aliasing module
+mod supersample;
+
+/// Parallel tiled rendering module
+mod tiles;
+
+/// Easing curves and tweens between shape states module
+mod tween;
+
+/// Recording surface module for asserting on drawing calls
+mod recording;
+
+/// Golden-image snapshot harness for rendering tests
+#[cfg(test)]
+pub(crate) mod golden;
+
 // Primary exports
 pub use self::point::Point;
 pub use self::line::Line;
 pub use self::rectangle::Rectangle;
 pub use self::triangle::Triangle;
 pub use self::circle::Circle;
-use raster::{Color, Image};
+use raster::Color;
 
 // Temporary exports (marked as bonus implementations)
 #[allow(unused_imports)]
 pub use self::pentagon::Pentagon;
 #[allow(unused_imports)]
 pub use self::cubes::Cubes;
+#[allow(unused_imports)]
+pub use self::polygon::Polygon;
+#[allow(unused_imports)]
+pub use self::mesh::{convex_hull, delaunay, voronoi};
+#[allow(unused_imports)]
+pub use self::triangulation::triangulate;
+#[allow(unused_imports)]
+pub use self::fill::FillStrategy;
+#[allow(unused_imports)]
+pub use self::boolean::{boolean, difference, intersection, union, xor, BooleanOp};
+#[allow(unused_imports)]
+pub use self::clip::{ClipMask, ClipRect, ClipStack};
+#[allow(unused_imports)]
+pub use self::recording::Recording;
+#[allow(unused_imports)]
+pub use self::paint::{Gradient, Paint, Painted, Spread};
+#[allow(unused_imports)]
+pub use self::pattern::Pattern;
+#[allow(unused_imports)]
+pub use self::dash::Dash;
+#[allow(unused_imports)]
+pub use self::style::{LineCap, LineJoin, Style, Styled};
+#[allow(unused_imports)]
+pub use self::color::{
+    ColorError, Hsl, Hsv, Interpolation, Lab, Oklab, Palette, PaletteKind, parse_color, to_hex,
+};
+#[allow(unused_imports)]
+pub use self::colormap::Colormap;
+#[allow(unused_imports)]
+pub use self::tween::{Animatable, Easing, Tween};
+#[allow(unused_imports)]
+pub use self::canvas::Canvas;
+#[allow(unused_imports)]
+pub use self::tiles::{TILE_SIZE, render_tiled};
+#[allow(unused_imports)]
+pub use self::supersample::{Downsample, Supersample};
+#[allow(unused_imports)]
+pub use self::flood::Connectivity;
 
 /// Trait for renderable objects
 /// 
 /// # Required Methods
-/// - `draw`: Renders the object to an image
+/// - `draw`: Renders the object to any displayable surface
 /// - `color`: Returns the base color of the object
+///
+/// # Provided Methods
+/// - `bounding_box`: Area the object can draw into, if known
+/// - `is_visible_on`: Whether drawing could change any pixel of a surface
 pub trait Drawable {
-    fn draw(&self, image: &mut Image);
+    fn draw(&self, image: &mut dyn Displayable);
     fn color(&self) -> Color;
+
+    fn bounding_box(&self) -> Option<ClipRect> {
+        None
+    }
+
+    /// Checks whether the bounding box reaches the surface's bounds
+    /// Shapes call it first in `draw` to skip work that would all be discarded
+    fn is_visible_on(&self, image: &dyn Displayable) -> bool {
+        self.bounding_box()
+            .is_none_or(|area| image.can_display(&area))
+    }
+
+    /// Draws the shape with `paint` in place of its own color
+    #[allow(dead_code)]
+    fn stroke(&self, image: &mut dyn Displayable, paint: &Paint) {
+        self.draw(&mut Painted::new(image, paint));
+    }
 }
 
 /// Trait for displayable surfaces
@@ -56,4 +176,190 @@ pub trait Drawable {
 pub trait Displayable {
     /// Sets the color of a specific pixel
     fn display(&mut self, x: i32, y: i32, color: Color);
+
+    /// Sets the color of every pixel from `left` to `right` inclusive on row `y`
+    /// Fills and strokes write runs through it, surfaces backed by memory
+    /// override it to write the whole run at once
+    fn display_span(&mut self, left: i32, right: i32, y: i32, color: Color) {
+        for x in left..=right {
+            self.display(x, y, color.clone());
+        }
+    }
+
+    /// Returns the only pixels that can be displayed, if limited
+    /// Rasterizers use it to skip work that would be discarded
+    fn bounds(&self) -> Option<ClipRect> {
+        None
+    }
+
+    /// Checks whether any pixel of `area` could be displayed
+    fn can_display(&self, area: &ClipRect) -> bool {
+        self.bounds().is_none_or(|bounds| bounds.intersects(area))
+    }
+}
+
+/// Trait for shapes that can be flattened into closed polygons
+///
+/// # With one Required method `to_polygons`
+/// Returns one polygon per outline held by the shape,
+/// each carrying the color it is drawn with
+#[allow(dead_code)]
+pub trait Polygonal {
+    fn to_polygons(&self) -> Vec<Polygon>;
+}
+
+/// Trait for shapes whose interior can be painted
+///
+/// Implemented for every `Polygonal` shape
+#[allow(dead_code)]
+pub trait Fillable {
+    /// Fills the shape's interior using the given strategy
+    fn fill(&self, image: &mut dyn Displayable, strategy: FillStrategy);
+
+    /// Fills the shape's interior with `paint` in place of its own color
+    fn fill_with(&self, image: &mut dyn Displayable, paint: &Paint, strategy: FillStrategy) {
+        self.fill(&mut Painted::new(image, paint), strategy);
+    }
+}
+
+// Property tests shared by every shape
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use raster::Image;
+
+    /// Coordinates biased towards the edges of the `i32` range
+    fn coordinate() -> impl Strategy<Value = i32> {
+        prop_oneof![
+            -100..100,
+            (i32::MAX - 1000)..=i32::MAX,
+            i32::MIN..=(i32::MIN + 1000),
+            any::<i32>(),
+        ]
+    }
+
+    fn point() -> impl Strategy<Value = Point> {
+        (coordinate(), coordinate()).prop_map(|(x, y)| Point::new(x, y))
+    }
+
+    proptest! {
+        // Property: drawing and filling never panic, whatever the coordinates
+        #[test]
+        fn prop_draw_never_panics(
+            a in point(),
+            b in point(),
+            c in point(),
+            size in prop_oneof![
+                -2000..2000,
+                Just(i32::MIN),
+                Just(i32::MAX),
+                any::<i32>(),
+            ],
+            thickness in -2..8,
+        ) {
+            let mut image = Image::blank(64, 64);
+
+            a.draw(&mut image);
+            Line::from_points(&a, &b, thickness, Color::white()).draw(&mut image);
+            Rectangle::new(&a, &b).draw(&mut image);
+            Triangle::new(&a, &b, &c).draw(&mut image);
+            Polygon::new(&[a.clone(), b.clone(), c.clone()]).draw(&mut image);
+            Cubes::new(&a, size).draw(&mut image);
+            Pentagon::new(&a, size).draw(&mut image);
+            Circle::new(&a, size).draw(&mut image);
+
+            Triangle::new(&a, &b, &c).fill(&mut image, FillStrategy::Scanline);
+            Rectangle::new(&a, &b).fill(&mut image, FillStrategy::Triangulated);
+        }
+
+        // Property: outlines write every pixel exactly once
+        #[test]
+        fn prop_draw_no_overdraw(
+            coords in prop::collection::vec((-150..150, -150..150), 3..8),
+            size in -150..150,
+        ) {
+            let vertices: Vec<Point> = coords.iter().map(|&(x, y)| Point::new(x, y)).collect();
+            let (a, b, c) = (&vertices[0], &vertices[1], &vertices[2]);
+            let shapes: [Box<dyn Drawable>; 7] = [
+                Box::new(a.clone()),
+                Box::new(Rectangle::new(a, b)),
+                Box::new(Triangle::new(a, b, c)),
+                Box::new(Polygon::new(&vertices).with_holes(vec![vertices[1..].to_vec()])),
+                Box::new(Circle::new(a, size)),
+                Box::new(Pentagon::new(a, size)),
+                Box::new(Cubes::new(a, size)),
+            ];
+
+            for shape in &shapes {
+                let mut recording = Recording::new();
+                shape.draw(&mut recording);
+                prop_assert_eq!(recording.overdraw(), 0);
+            }
+        }
+    }
+
+    // Test shapes are only visible on surfaces their bounding box reaches
+    #[test]
+    fn test_is_visible_on() {
+        let image = Image::blank(100, 100);
+        assert!(Circle::new(&Point::new(50, 50), 10).is_visible_on(&image));
+        assert!(Circle::new(&Point::new(-20, 50), 30).is_visible_on(&image));
+        assert!(!Circle::new(&Point::new(500, 500), 30).is_visible_on(&image));
+        assert!(!Line::new(&Point::new(-50, -5), &Point::new(-10, -40)).is_visible_on(&image));
+
+        // Surfaces without bounds can display anything
+        assert!(Circle::new(&Point::new(500, 500), 30).is_visible_on(&Recording::new()));
+    }
+
+    // Test every `new`, `from_*` and `random_in` constructor strokes in white
+    #[test]
+    fn test_constructors_never_random_color() {
+        let (a, b, c) = (Point::new(10, 10), Point::new(40, 10), Point::new(25, 40));
+        let shapes: Vec<(&str, Box<dyn Drawable>)> = vec![
+            ("Point::new", Box::new(a.clone())),
+            ("Line::new", Box::new(Line::new(&a, &b))),
+            ("Rectangle::new", Box::new(Rectangle::new(&a, &b))),
+            (
+                "Rectangle::from_corners",
+                Box::new(Rectangle::from_corners(&a, &c)),
+            ),
+            (
+                "Rectangle::from_origin_size",
+                Box::new(Rectangle::from_origin_size(&a, 20, 30)),
+            ),
+            (
+                "Rectangle::from_center_size",
+                Box::new(Rectangle::from_center_size(&c, 20, 30)),
+            ),
+            (
+                "Rectangle::random_in",
+                Box::new(Rectangle::random_in(100, 100)),
+            ),
+            ("Triangle::new", Box::new(Triangle::new(&a, &b, &c))),
+            (
+                "Triangle::from_vertices",
+                Box::new(Triangle::from_vertices(&a, &b, &c)),
+            ),
+            (
+                "Triangle::random_in",
+                Box::new(Triangle::random_in(100, 100)),
+            ),
+            ("Circle::new", Box::new(Circle::new(&c, 20))),
+            ("Circle::from_center", Box::new(Circle::from_center(&c, 20))),
+            ("Cubes::new", Box::new(Cubes::new(&c, 20))),
+            ("Cubes::from_center", Box::new(Cubes::from_center(&c, 20))),
+            ("Pentagon::new", Box::new(Pentagon::new(&c, 20))),
+            (
+                "Pentagon::from_center",
+                Box::new(Pentagon::from_center(&c, 20)),
+            ),
+            ("Polygon::new", Box::new(Polygon::new(&[a, b, c]))),
+        ];
+
+        for (name, shape) in &shapes {
+            let color = shape.color();
+            assert_eq!((color.r, color.g, color.b), (255, 255, 255), "{name}");
+        }
+    }
 }
@@ -0,0 +1,469 @@
+use super::{ClipRect, Displayable, Interpolation, Pattern, Point};
+use raster::Color;
+use std::f64::consts::PI;
+
+/// How a gradient continues past its first and last stop
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Spread {
+    /// Extends the end colors
+    #[default]
+    Pad,
+    /// Starts over from the first stop
+    Repeat,
+    /// Runs back and forth between the end stops
+    Reflect,
+}
+
+/// Ordered color stops along a gradient, positioned between 0.0 and 1.0
+#[derive(Debug, Clone)]
+pub struct Gradient {
+    stops: Vec<(f64, Color)>,
+    spread: Spread,
+    interpolation: Interpolation,
+}
+
+#[allow(dead_code)]
+impl Gradient {
+    /// Creates a gradient from `(offset, color)` stops
+    /// Offsets are clamped to 0.0..=1.0 and sorted, equal offsets make hard edges
+    pub fn new(stops: &[(f64, Color)]) -> Self {
+        let mut stops: Vec<(f64, Color)> = stops
+            .iter()
+            .map(|(offset, color)| (offset.clamp(0.0, 1.0), color.clone()))
+            .collect();
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        Gradient {
+            stops,
+            spread: Spread::Pad,
+            interpolation: Interpolation::default(),
+        }
+    }
+
+    /// Creates a gradient running evenly from `from` to `to`
+    pub fn between(from: Color, to: Color) -> Self {
+        Gradient::new(&[(0.0, from), (1.0, to)])
+    }
+
+    /// Sets how the gradient continues past its end stops
+    pub fn with_spread(mut self, spread: Spread) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    /// Sets the color space neighbouring stops are blended in
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Returns the stops in offset order
+    pub fn stops(&self) -> &[(f64, Color)] {
+        &self.stops
+    }
+
+    /// Color at position `t`, where 0.0 is the first stop and 1.0 the last
+    /// Positions outside that range follow the spread mode
+    pub fn color_at(&self, t: f64) -> Color {
+        let t = match self.spread {
+            _ if !t.is_finite() => 1.0,
+            Spread::Pad => t.clamp(0.0, 1.0),
+            Spread::Repeat => t.rem_euclid(1.0),
+            Spread::Reflect => {
+                let t = t.rem_euclid(2.0);
+                if t > 1.0 { 2.0 - t } else { t }
+            }
+        };
+
+        let Some((first, last)) = self.stops.first().zip(self.stops.last()) else {
+            return Color::rgba(0, 0, 0, 0);
+        };
+        if t <= first.0 {
+            return first.1.clone();
+        }
+        if t >= last.0 {
+            return last.1.clone();
+        }
+
+        let i = self.stops.partition_point(|(offset, _)| *offset <= t);
+        let (a, b) = (&self.stops[i - 1], &self.stops[i]);
+        self.interpolation.mix(&a.1, &b.1, (t - a.0) / (b.0 - a.0))
+    }
+}
+
+/// Blends two colors channel by channel, `t` running from `a` to `b`
+pub(super) fn lerp(a: &Color, b: &Color, t: f64) -> Color {
+    let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+    Color::rgba(mix(a.r, b.r), mix(a.g, b.g), mix(a.b, b.b), mix(a.a, b.a))
+}
+
+/// What a shape is painted with, sampled at each pixel's center
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub enum Paint {
+    /// A single flat color
+    Solid(Color),
+    /// Gradient running along the line from `start` (0.0) to `end` (1.0)
+    Linear {
+        start: (f64, f64),
+        end: (f64, f64),
+        gradient: Gradient,
+    },
+    /// Gradient running outwards from `center` (0.0) to `radius` (1.0)
+    Radial {
+        center: (f64, f64),
+        radius: f64,
+        gradient: Gradient,
+    },
+    /// Gradient sweeping clockwise around `center`, starting at `angle` radians
+    /// from the positive x axis
+    Conic {
+        center: (f64, f64),
+        angle: f64,
+        gradient: Gradient,
+    },
+    /// Repeating hatch, dot, checkerboard or image pattern
+    Pattern(Pattern),
+}
+
+#[allow(dead_code)]
+impl Paint {
+    /// Creates a linear gradient paint between two points
+    pub fn linear(start: &Point, end: &Point, gradient: Gradient) -> Self {
+        Paint::Linear {
+            start: (start.x as f64 + 0.5, start.y as f64 + 0.5),
+            end: (end.x as f64 + 0.5, end.y as f64 + 0.5),
+            gradient,
+        }
+    }
+
+    /// Creates a radial gradient paint around a center point
+    pub fn radial(center: &Point, radius: i32, gradient: Gradient) -> Self {
+        Paint::Radial {
+            center: (center.x as f64 + 0.5, center.y as f64 + 0.5),
+            radius: radius as f64,
+            gradient,
+        }
+    }
+
+    /// Creates a conic gradient paint around a center point
+    pub fn conic(center: &Point, angle: f64, gradient: Gradient) -> Self {
+        Paint::Conic {
+            center: (center.x as f64 + 0.5, center.y as f64 + 0.5),
+            angle,
+            gradient,
+        }
+    }
+
+    /// Color of the paint at pixel `(x, y)`,
+    /// `None` where a pattern leaves the pixel untouched
+    pub fn color_at(&self, x: i32, y: i32) -> Option<Color> {
+        let (px, py) = (x as f64 + 0.5, y as f64 + 0.5);
+
+        let color = match self {
+            Paint::Solid(color) => color.clone(),
+            Paint::Linear {
+                start,
+                end,
+                gradient,
+            } => {
+                let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+                let length = dx * dx + dy * dy;
+                let t = if length == 0.0 {
+                    0.0
+                } else {
+                    ((px - start.0) * dx + (py - start.1) * dy) / length
+                };
+                gradient.color_at(t)
+            }
+            Paint::Radial {
+                center,
+                radius,
+                gradient,
+            } => {
+                let distance = (px - center.0).hypot(py - center.1);
+                gradient.color_at(distance / radius)
+            }
+            Paint::Conic {
+                center,
+                angle,
+                gradient,
+            } => {
+                let theta = (py - center.1).atan2(px - center.0);
+                gradient.color_at((theta - angle).rem_euclid(2.0 * PI) / (2.0 * PI))
+            }
+            Paint::Pattern(pattern) => return pattern.color_at(x, y),
+        };
+        Some(color)
+    }
+}
+
+impl From<Color> for Paint {
+    fn from(color: Color) -> Self {
+        Paint::Solid(color)
+    }
+}
+
+impl From<Pattern> for Paint {
+    fn from(pattern: Pattern) -> Self {
+        Paint::Pattern(pattern)
+    }
+}
+
+/// Surface adapter replacing the color of every write with a paint
+/// Lets any shape be stroked or filled with a gradient or pattern
+pub struct Painted<'a> {
+    target: &'a mut dyn Displayable,
+    paint: &'a Paint,
+    opacity: f64,
+}
+
+#[allow(dead_code)]
+impl<'a> Painted<'a> {
+    /// Wraps a surface so every pixel drawn on it takes its color from `paint`
+    pub fn new(target: &'a mut dyn Displayable, paint: &'a Paint) -> Self {
+        Painted {
+            target,
+            paint,
+            opacity: 1.0,
+        }
+    }
+
+    /// Scales the alpha of every color written by `opacity`, from 0.0 to 1.0
+    pub fn with_opacity(mut self, opacity: f64) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+}
+
+impl Painted<'_> {
+    /// Scales the alpha of a painted color by the opacity
+    fn faded(&self, mut color: Color) -> Color {
+        if self.opacity < 1.0 {
+            color.a = (color.a as f64 * self.opacity).round() as u8;
+        }
+        color
+    }
+}
+
+impl Displayable for Painted<'_> {
+    fn display(&mut self, x: i32, y: i32, _color: Color) {
+        if let Some(color) = self.paint.color_at(x, y) {
+            let color = self.faded(color);
+            self.target.display(x, y, color);
+        }
+    }
+
+    /// Solid paints hand the whole run to the target at once,
+    /// gradients and patterns are colored pixel by pixel
+    fn display_span(&mut self, left: i32, right: i32, y: i32, color: Color) {
+        if let Paint::Solid(solid) = self.paint {
+            let solid = self.faded(solid.clone());
+            self.target.display_span(left, right, y, solid);
+        } else {
+            for x in left..=right {
+                self.display(x, y, color.clone());
+            }
+        }
+    }
+
+    fn bounds(&self) -> Option<ClipRect> {
+        self.target.bounds()
+    }
+}
+
+// Unit tests for paints and gradients
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometrical_shapes::golden::{self, Tolerance};
+    use crate::geometrical_shapes::{
+        Circle, Drawable, FillStrategy, Fillable, Pentagon, Polygon, Recording, Rectangle, Styled,
+    };
+    use raster::Image;
+
+    fn rgb(color: &Color) -> (u8, u8, u8) {
+        (color.r, color.g, color.b)
+    }
+
+    fn black_to_white() -> Gradient {
+        Gradient::between(Color::black(), Color::white())
+    }
+
+    // Test stops are sorted, clamped and interpolated
+    #[test]
+    fn test_gradient_stops() {
+        let gradient = Gradient::new(&[
+            (1.5, Color::blue()),
+            (0.0, Color::red()),
+            (0.5, Color::green()),
+        ]);
+
+        assert_eq!(gradient.stops().len(), 3);
+        assert_eq!(gradient.stops()[2].0, 1.0);
+        assert_eq!(rgb(&gradient.color_at(0.0)), (255, 0, 0));
+        assert_eq!(rgb(&gradient.color_at(0.25)), (128, 128, 0));
+        assert_eq!(rgb(&gradient.color_at(0.5)), (0, 255, 0));
+        assert_eq!(rgb(&gradient.color_at(1.0)), (0, 0, 255));
+
+        // Equal offsets switch colors without blending
+        let hard = Gradient::new(&[
+            (0.0, Color::red()),
+            (0.5, Color::red()),
+            (0.5, Color::blue()),
+            (1.0, Color::blue()),
+        ]);
+        assert_eq!(rgb(&hard.color_at(0.49)), (255, 0, 0));
+        assert_eq!(rgb(&hard.color_at(0.5)), (0, 0, 255));
+
+        assert_eq!(Gradient::new(&[]).color_at(0.5).a, 0);
+    }
+
+    // Test pad, repeat and reflect outside the stops
+    #[test]
+    fn test_gradient_spread() {
+        let pad = black_to_white();
+        assert_eq!(pad.color_at(-1.0).r, 0);
+        assert_eq!(pad.color_at(1.75).r, 255);
+
+        let repeat = black_to_white().with_spread(Spread::Repeat);
+        assert_eq!(repeat.color_at(1.25).r, repeat.color_at(0.25).r);
+        assert_eq!(repeat.color_at(-0.75).r, repeat.color_at(0.25).r);
+
+        let reflect = black_to_white().with_spread(Spread::Reflect);
+        assert_eq!(reflect.color_at(1.25).r, reflect.color_at(0.75).r);
+        assert_eq!(reflect.color_at(-0.25).r, reflect.color_at(0.25).r);
+        assert_eq!(reflect.color_at(2.0).r, 0);
+    }
+
+    // Test linear, radial and conic paints sample at pixel centers
+    #[test]
+    fn test_paint_geometry() {
+        let linear = Paint::linear(&Point::new(0, 0), &Point::new(10, 0), black_to_white());
+        assert_eq!(linear.color_at(0, 7).unwrap().r, 0);
+        assert_eq!(linear.color_at(5, 3).unwrap().r, 128);
+        assert_eq!(linear.color_at(10, 0).unwrap().r, 255);
+        assert_eq!(linear.color_at(50, 0).unwrap().r, 255);
+
+        let radial = Paint::radial(&Point::new(20, 20), 10, black_to_white());
+        assert_eq!(radial.color_at(20, 20).unwrap().r, 0);
+        assert_eq!(radial.color_at(25, 20).unwrap().r, 128);
+        assert_eq!(radial.color_at(20, 5).unwrap().r, 255);
+
+        let conic = Paint::conic(&Point::new(0, 0), 0.0, black_to_white());
+        assert_eq!(conic.color_at(10, 0).unwrap().r, 0);
+        // A quarter turn clockwise in image coordinates points down
+        assert!((conic.color_at(0, 10).unwrap().r as i32 - 64).abs() <= 1);
+        assert!((conic.color_at(-10, 0).unwrap().r as i32 - 128).abs() <= 1);
+
+        let degenerate = Paint::radial(&Point::new(0, 0), 0, black_to_white());
+        assert_eq!(degenerate.color_at(0, 0).unwrap().r, 255);
+
+        let solid: Paint = Color::red().into();
+        assert_eq!(rgb(&solid.color_at(123, -4).unwrap()), (255, 0, 0));
+    }
+
+    // Test the paint replaces the shape's own color for strokes and fills
+    #[test]
+    fn test_painted_surface() {
+        let paint = Paint::linear(&Point::new(0, 0), &Point::new(20, 0), black_to_white());
+        let rectangle = Rectangle::new(&Point::new(0, 0), &Point::new(2, 2));
+
+        let mut recording = Recording::new();
+        rectangle.stroke(&mut recording, &paint);
+        for (x, y, color) in recording.writes() {
+            assert_eq!(rgb(color), rgb(&paint.color_at(*x, *y).unwrap()));
+        }
+
+        let mut recording = Recording::new();
+        rectangle.fill_with(&mut recording, &paint, FillStrategy::Scanline);
+        assert!(!recording.writes().is_empty());
+        for (x, y, color) in recording.writes() {
+            assert_eq!(rgb(color), rgb(&paint.color_at(*x, *y).unwrap()));
+        }
+    }
+
+    // Test solid fills and strokes reach the surface as whole spans
+    #[test]
+    fn test_painted_spans() {
+        let rectangle = Rectangle::from_corners(&Point::new(10, 10), &Point::new(40, 30))
+            .with_stroke(Color::red())
+            .with_fill(Color::rgba(0, 0, 255, 200))
+            .with_opacity(0.5);
+        let polygon = Polygon::new(&[Point::new(5, 5), Point::new(60, 20), Point::new(20, 50)])
+            .with_fill(Color::green());
+
+        for shape in [&rectangle as &dyn Drawable, &polygon] {
+            let mut recording = Recording::with_bounds(ClipRect::from_size(64, 64));
+            shape.draw(&mut recording);
+            assert!(!recording.spans().is_empty());
+            // Every pixel write comes from a span
+            let spanned: usize = recording
+                .spans()
+                .iter()
+                .map(|&(left, right, _)| (right - left + 1) as usize)
+                .sum();
+            assert_eq!(spanned, recording.writes().len());
+        }
+
+        // Opacity is applied once for the whole span
+        let mut recording = Recording::new();
+        rectangle.draw(&mut recording);
+        assert_eq!(recording.color_at(20, 20).unwrap().a, 100);
+        let stroke = recording.writes().iter().filter(|(_, _, color)| color.r == 255);
+        assert!(stroke.map(|(_, _, color)| color.a).all(|alpha| alpha == 128));
+
+        // Gradients still color each pixel of the span on its own
+        let paint = Paint::linear(&Point::new(0, 0), &Point::new(20, 0), black_to_white());
+        let mut recording = Recording::new();
+        Painted::new(&mut recording, &paint).display_span(0, 20, 0, Color::red());
+        assert!(recording.spans().is_empty());
+        assert_eq!(rgb(recording.color_at(20, 0).unwrap()), (255, 255, 255));
+    }
+
+    // Test gradient fills and strokes against their golden image
+    #[test]
+    fn test_paint_golden() {
+        let mut image = Image::blank(120, 120);
+        let stops = Gradient::new(&[
+            (0.0, Color::red()),
+            (0.5, Color::green()),
+            (1.0, Color::blue()),
+        ]);
+
+        let linear = Paint::linear(
+            &Point::new(0, 0),
+            &Point::new(30, 30),
+            stops.clone().with_spread(Spread::Reflect),
+        );
+        let square = [
+            Point::new(5, 5),
+            Point::new(50, 5),
+            Point::new(50, 50),
+            Point::new(5, 50),
+        ];
+        Polygon::from_vertices(&square, Color::white()).fill_with(
+            &mut image,
+            &linear,
+            FillStrategy::Scanline,
+        );
+
+        let radial = Paint::radial(
+            &Point::new(85, 30),
+            12,
+            stops.clone().with_spread(Spread::Repeat),
+        );
+        Circle::new(&Point::new(85, 30), 28).fill_with(&mut image, &radial, FillStrategy::Scanline);
+
+        let conic = Paint::conic(&Point::new(60, 85), 0.0, stops);
+        Pentagon::new(&Point::new(60, 85), 30).fill_with(
+            &mut image,
+            &conic,
+            FillStrategy::Triangulated,
+        );
+        Circle::new(&Point::new(60, 85), 33).stroke(&mut image, &conic);
+
+        golden::assert_golden("paint", &image, Tolerance::EXACT);
+    }
+}
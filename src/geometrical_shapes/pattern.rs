@@ -0,0 +1,287 @@
+use raster::{Color, Image};
+
+/// Repeating fill pattern, anchored at the image origin so neighbouring
+/// shapes filled with the same pattern line up
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    /// Parallel lines `width` pixels wide, `spacing` pixels apart,
+    /// running at `angle` radians from the positive x axis
+    Hatch {
+        angle: f64,
+        spacing: i32,
+        width: i32,
+        color: Color,
+    },
+    /// Two hatches crossing at right angles
+    CrossHatch {
+        angle: f64,
+        spacing: i32,
+        width: i32,
+        color: Color,
+    },
+    /// Round dots of `radius` on a square grid `spacing` pixels apart
+    Dots {
+        spacing: i32,
+        radius: f64,
+        color: Color,
+    },
+    /// Alternating squares of `size` pixels, starting with `first` at the origin
+    Checkerboard {
+        size: i32,
+        first: Color,
+        second: Color,
+    },
+    /// An image repeated edge to edge
+    Tile(Image),
+}
+
+#[allow(dead_code)]
+impl Pattern {
+    /// Creates 1px hatch lines at `angle` radians, `spacing` pixels apart
+    pub fn hatch(angle: f64, spacing: i32, color: Color) -> Self {
+        Pattern::Hatch {
+            angle,
+            spacing,
+            width: 1,
+            color,
+        }
+    }
+
+    /// Creates 1px cross-hatch lines at `angle` radians and its perpendicular
+    pub fn cross_hatch(angle: f64, spacing: i32, color: Color) -> Self {
+        Pattern::CrossHatch {
+            angle,
+            spacing,
+            width: 1,
+            color,
+        }
+    }
+
+    /// Creates a grid of dots
+    pub fn dots(spacing: i32, radius: f64, color: Color) -> Self {
+        Pattern::Dots {
+            spacing,
+            radius,
+            color,
+        }
+    }
+
+    /// Creates a checkerboard of two colors
+    pub fn checkerboard(size: i32, first: Color, second: Color) -> Self {
+        Pattern::Checkerboard {
+            size,
+            first,
+            second,
+        }
+    }
+
+    /// Tiles the given image
+    pub fn tile(image: Image) -> Self {
+        Pattern::Tile(image)
+    }
+
+    /// Color of the pattern at pixel `(x, y)`,
+    /// `None` where the pattern leaves the pixel untouched
+    pub fn color_at(&self, x: i32, y: i32) -> Option<Color> {
+        let (px, py) = (x as f64 + 0.5, y as f64 + 0.5);
+
+        match self {
+            Pattern::Hatch {
+                angle,
+                spacing,
+                width,
+                color,
+            } => on_hatch(px, py, *angle, *spacing, *width).then(|| color.clone()),
+            Pattern::CrossHatch {
+                angle,
+                spacing,
+                width,
+                color,
+            } => (on_hatch(px, py, *angle, *spacing, *width)
+                || on_hatch(
+                    px,
+                    py,
+                    angle - std::f64::consts::FRAC_PI_2,
+                    *spacing,
+                    *width,
+                ))
+            .then(|| color.clone()),
+            Pattern::Dots {
+                spacing,
+                radius,
+                color,
+            } => {
+                let spacing = (*spacing).max(1) as f64;
+                let half = spacing / 2.0;
+                let dx = px.rem_euclid(spacing) - half;
+                let dy = py.rem_euclid(spacing) - half;
+                (dx.hypot(dy) <= *radius).then(|| color.clone())
+            }
+            Pattern::Checkerboard {
+                size,
+                first,
+                second,
+            } => {
+                let size = (*size).max(1);
+                let parity = (x.div_euclid(size) as i64 + y.div_euclid(size) as i64) % 2;
+                Some(if parity == 0 { first } else { second }.clone())
+            }
+            Pattern::Tile(image) => {
+                if image.width <= 0 || image.height <= 0 {
+                    return None;
+                }
+                image
+                    .get_pixel(x.rem_euclid(image.width), y.rem_euclid(image.height))
+                    .ok()
+            }
+        }
+    }
+}
+
+/// Checks whether a pixel center lies on a hatch line
+/// Measures the distance along the lines' normal, modulo the spacing
+fn on_hatch(px: f64, py: f64, angle: f64, spacing: i32, width: i32) -> bool {
+    let offset = py * angle.cos() - px * angle.sin();
+    offset.rem_euclid(spacing.max(1) as f64) < width as f64
+}
+
+// Unit tests for fill patterns
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::FRAC_PI_4;
+
+    fn covered(pattern: &Pattern, size: i32) -> Vec<(i32, i32)> {
+        let mut pixels = Vec::new();
+        for y in 0..size {
+            for x in 0..size {
+                if pattern.color_at(x, y).is_some() {
+                    pixels.push((x, y));
+                }
+            }
+        }
+        pixels
+    }
+
+    // Test horizontal hatches cover one row in every `spacing`
+    #[test]
+    fn test_pattern_hatch() {
+        let hatch = Pattern::hatch(0.0, 4, Color::white());
+        let pixels = covered(&hatch, 16);
+
+        assert_eq!(pixels.len(), 4 * 16);
+        assert!(pixels.iter().all(|&(_, y)| y % 4 == 0));
+
+        // Negative coordinates continue the same rhythm
+        assert!(hatch.color_at(-3, -4).is_some());
+        assert!(hatch.color_at(-3, -5).is_none());
+
+        // Diagonal hatches run at 45 degrees
+        let diagonal = Pattern::hatch(FRAC_PI_4, 8, Color::white());
+        for (x, y) in covered(&diagonal, 16) {
+            assert!(diagonal.color_at(x + 1, y + 1).is_some());
+        }
+    }
+
+    // Test cross-hatching is the union of both directions
+    #[test]
+    fn test_pattern_cross_hatch() {
+        let cross = Pattern::cross_hatch(0.0, 5, Color::white());
+        let pixels = covered(&cross, 10);
+
+        assert_eq!(pixels.len(), 2 * 10 + 2 * 10 - 4);
+        assert!(cross.color_at(0, 3).is_some());
+        assert!(cross.color_at(3, 0).is_some());
+        assert!(cross.color_at(3, 3).is_none());
+    }
+
+    // Test dots sit at the center of each grid cell
+    #[test]
+    fn test_pattern_dots() {
+        let dots = Pattern::dots(10, 1.0, Color::white());
+
+        assert!(dots.color_at(5, 5).is_some());
+        assert!(dots.color_at(15, -5).is_some());
+        assert!(dots.color_at(0, 0).is_none());
+        assert_eq!(covered(&dots, 10).len(), 4);
+    }
+
+    // Test checkerboard squares alternate in both directions
+    #[test]
+    fn test_pattern_checkerboard() {
+        let board = Pattern::checkerboard(2, Color::white(), Color::black());
+
+        assert_eq!(board.color_at(0, 0).unwrap().r, 255);
+        assert_eq!(board.color_at(1, 1).unwrap().r, 255);
+        assert_eq!(board.color_at(2, 0).unwrap().r, 0);
+        assert_eq!(board.color_at(-1, 0).unwrap().r, 0);
+        assert_eq!(covered(&board, 8).len(), 64);
+    }
+
+    // Test image tiles wrap around in both directions
+    #[test]
+    fn test_pattern_tile() {
+        let mut image = Image::blank(2, 3);
+        image.set_pixel(1, 2, Color::red()).unwrap();
+        let tile = Pattern::tile(image);
+
+        assert_eq!(tile.color_at(1, 2).unwrap().r, 255);
+        assert_eq!(tile.color_at(5, 8).unwrap().r, 255);
+        assert_eq!(tile.color_at(-1, -1).unwrap().r, 255);
+        assert_eq!(tile.color_at(0, 2).unwrap().r, 0);
+    }
+
+    // Test pattern fills on every kind of shape against their golden image
+    #[test]
+    fn test_pattern_golden() {
+        use crate::geometrical_shapes::golden::{self, Tolerance};
+        use crate::geometrical_shapes::{
+            Circle, FillStrategy, Fillable, Paint, Pentagon, Point, Polygon, Rectangle, Triangle,
+        };
+
+        let mut image = Image::blank(160, 160);
+        let mut swatch = Image::blank(4, 4);
+        swatch.set_pixel(0, 0, Color::red()).unwrap();
+        swatch.set_pixel(1, 1, Color::green()).unwrap();
+        swatch.set_pixel(2, 2, Color::blue()).unwrap();
+
+        let fills: [(&dyn Fillable, Pattern); 5] = [
+            (
+                &Rectangle::new(&Point::new(0, 35), &Point::new(15, 5)),
+                Pattern::hatch(FRAC_PI_4, 6, Color::white()),
+            ),
+            (
+                &Triangle::new(&Point::new(60, 5), &Point::new(110, 5), &Point::new(85, 50)),
+                Pattern::cross_hatch(0.3, 7, Color::rgb(255, 200, 0)),
+            ),
+            (
+                &Circle::new(&Point::new(135, 30), 22),
+                Pattern::dots(6, 1.6, Color::rgb(0, 200, 255)),
+            ),
+            (
+                &Pentagon::new(&Point::new(40, 110), 35),
+                Pattern::checkerboard(5, Color::white(), Color::rgb(80, 80, 80)),
+            ),
+            (
+                &Polygon::from_vertices(
+                    &[
+                        Point::new(90, 80),
+                        Point::new(150, 85),
+                        Point::new(120, 110),
+                        Point::new(150, 150),
+                        Point::new(95, 145),
+                    ],
+                    Color::white(),
+                ),
+                Pattern::tile(swatch),
+            ),
+        ];
+
+        for (shape, pattern) in fills {
+            shape.fill_with(&mut image, &Paint::from(pattern), FillStrategy::Scanline);
+        }
+
+        golden::assert_golden("pattern", &image, Tolerance::EXACT);
+    }
+}
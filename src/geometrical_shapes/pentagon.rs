@@ -1,32 +1,33 @@
-use super::{Drawable, Line, Point};
+use super::stroke::StrokeMask;
+use super::{
+    Animatable, ClipRect, Displayable, Drawable, Point, Polygon, Polygonal, Style, Styled,
+};
 use rand::Rng;
-use raster::{Color, Image};
+use raster::Color;
 use std::f64::consts::PI;
 
-/// Represents a pentagon shape with center, radius and color
+/// Represents a pentagon shape with center, radius and a style
 /// Can contain multiple pentagons for grouped rendering
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Pentagon {
-    pentagons: Vec<(Point, i32, Color)>,
+    pentagons: Vec<(Point, i32)>,
+    style: Style,
 }
 
 #[allow(dead_code)]
 impl Pentagon {
     /// Creates a new pentagon with specified center and radius
-    /// Generates a random vibrant color (RGB 100-255)
+    /// Kept for compatibility, same as `from_center`
     pub fn new(center: &Point, radius: i32) -> Self {
-        let mut rng = rand::thread_rng();
-        let mut pentagons = Vec::new();
-
-        let color = Color::rgb(
-            rng.gen_range(100..255),
-            rng.gen_range(100..255),
-            rng.gen_range(100..255),
-        );
-
-        pentagons.push((center.clone(), radius, color.clone()));
+        Pentagon::from_center(center, radius)
+    }
 
-        Pentagon { pentagons }
+    /// Creates a pentagon with specified center and radius, stroked 2px wide in white
+    pub fn from_center(center: &Point, radius: i32) -> Self {
+        Pentagon {
+            pentagons: vec![(center.clone(), radius)],
+            style: Style::new().width(2),
+        }
     }
 
     /// Generates a random pentagon within specified bounds
@@ -38,14 +39,12 @@ impl Pentagon {
 
         let center = Point::random(width, height);
         let radius = rng.gen_range(30..80);
-        let color = Color::rgb(
-            rng.gen_range(100..255),
-            rng.gen_range(100..255),
-            rng.gen_range(100..255),
-        );
-        pentagons.push((center, radius, color));
+        pentagons.push((center, radius));
 
-        Pentagon { pentagons }
+        Pentagon {
+            pentagons,
+            style: Style::random(100..255).width(2),
+        }
     }
 
     /// Calculates vertex positions for a regular pentagon
@@ -54,39 +53,84 @@ impl Pentagon {
         (0..5)
             .map(|i| {
                 let angle = 2.0 * PI * i as f64 / 5.0;
-                Point::new(
-                    center.x + (radius as f64 * angle.cos()) as i32,
-                    center.y + (radius as f64 * angle.sin()) as i32,
-                )
+                let dx = (radius as f64 * angle.cos()) as i32;
+                let dy = (radius as f64 * angle.sin()) as i32;
+                Point::new(center.x.saturating_add(dx), center.y.saturating_add(dy))
             })
             .collect()
     }
 }
 
+impl Styled for Pentagon {
+    fn style(&self) -> &Style {
+        &self.style
+    }
+
+    fn style_mut(&mut self) -> &mut Style {
+        &mut self.style
+    }
+}
+
+impl Animatable for Pentagon {
+    /// Moves and resizes every pentagon and blends the style
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        Pentagon {
+            pentagons: self.pentagons.interpolate(&to.pentagons, t),
+            style: self.style.interpolate(&to.style, t),
+        }
+    }
+}
+
 impl Drawable for Pentagon {
     /// Renders the pentagon using line segments
-    /// Draws 5 edges with consistent 2px thickness
+    /// Draws 5 edges with the style's width, each pixel once
     /// Implements Drawable trait requirement
-    fn draw(&self, image: &mut Image) {
-        let thickness = 2;
+    fn draw(&self, image: &mut dyn Displayable) {
+        if !self.is_visible_on(image) {
+            return;
+        }
 
-        for (center, radius, color) in &self.pentagons {
+        for (center, radius) in &self.pentagons {
             let vertices = Pentagon::get_vertices(center, *radius);
+            self.style.paint_fill(&[&vertices], image);
 
-            for i in 0..5 {
-                let start = &vertices[i];
-                let end = &vertices[(i + 1) % 5];
-
-                let line = Line::from_points(start, end, thickness, color.clone());
-                line.draw(image);
-            }
+            let mut mask = StrokeMask::new(image).styled(&self.style);
+            mask.add_ring(&vertices, self.style.width);
+            self.style.paint_stroke(mask, image);
         }
     }
 
-    /// Returns default color for pentagon (black)
+    /// Returns the color of the pentagon's style
     /// Implements Drawable trait requirement
     fn color(&self) -> Color {
-        Color::rgb(0, 0, 0)
+        self.style.color()
+    }
+
+    /// Covers the circumscribed circle of every pentagon
+    fn bounding_box(&self) -> Option<ClipRect> {
+        self.pentagons
+            .iter()
+            .map(|(center, radius)| {
+                ClipRect::new(center.x, center.y, center.x, center.y).expand(
+                    radius
+                        .saturating_abs()
+                        .saturating_add(self.style.reach().max(1)),
+                )
+            })
+            .reduce(|a, b| a.union(&b))
+    }
+}
+
+impl Polygonal for Pentagon {
+    /// Flattens each pentagon into its five vertices
+    fn to_polygons(&self) -> Vec<Polygon> {
+        self.pentagons
+            .iter()
+            .map(|(center, radius)| {
+                Polygon::from_vertices(&Pentagon::get_vertices(center, *radius), self.color())
+                    .with_style(self.style.clone())
+            })
+            .collect()
     }
 }
 
@@ -94,6 +138,7 @@ impl Drawable for Pentagon {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::geometrical_shapes::golden::{self, Tolerance};
 
     // Test basic pentagon creation with expected properties
     #[test]
@@ -103,7 +148,7 @@ mod tests {
         let pentagon = Pentagon::new(&center, radius);
 
         assert_eq!(pentagon.pentagons.len(), 1);
-        let (pent_center, pent_radius, _color) = &pentagon.pentagons[0];
+        let (pent_center, pent_radius) = &pentagon.pentagons[0];
         assert_eq!(pent_center.x, center.x);
         assert_eq!(pent_center.y, center.y);
         assert_eq!(*pent_radius, radius);
@@ -115,7 +160,7 @@ mod tests {
         let pentagon = Pentagon::random(800, 800);
 
         // Check each pentagon's properties
-        for (center, radius, _color) in &pentagon.pentagons {
+        for (center, radius) in &pentagon.pentagons {
             assert!(center.x >= 0 && center.x <= 800);
             assert!(center.y >= 0 && center.y <= 800);
             assert!(*radius >= 30 && *radius <= 80);
@@ -140,18 +185,32 @@ mod tests {
         }
     }
 
+    // Test flattening reuses the computed vertices
+    #[test]
+    fn test_pentagon_to_polygons() {
+        let center = Point::new(100, 100);
+        let pentagon = Pentagon::new(&center, 50);
+        let polygons = pentagon.to_polygons();
+
+        assert_eq!(polygons.len(), 1);
+        assert_eq!(
+            polygons[0].vertices(),
+            Pentagon::get_vertices(&center, 50).as_slice()
+        );
+    }
+
     // Test pentagon rendering modifies the image
     #[test]
     fn test_pentagon_draw() {
         let center = Point::new(100, 100);
         let radius = 50;
-        let pentagon = Pentagon::new(&center, radius);
+        let pentagon = Pentagon::new(&center, radius).with_stroke(Color::rgb(255, 0, 255));
 
-        let mut image = Image::blank(800, 800);
-        pentagon.draw(&mut image);
+        let image = golden::render(200, 200, &[&pentagon]);
+        golden::assert_golden("pentagon", &image, Tolerance::EXACT);
     }
 
-    // Test color getter returns default black color
+    // Test color getter returns the stroke color of the style
     #[test]
     fn test_pentagon_color() {
         let center = Point::new(100, 100);
@@ -159,9 +218,11 @@ mod tests {
         let pentagon = Pentagon::new(&center, radius);
 
         let color = pentagon.color();
-        assert_eq!(color.r, 0);
-        assert_eq!(color.g, 0);
-        assert_eq!(color.b, 0);
+        assert_eq!((color.r, color.g, color.b), (255, 255, 255));
+
+        let filled = Pentagon::new(&center, radius)
+            .with_style(Style::new().no_stroke().fill(Color::green()));
+        assert_eq!(filled.color().g, 255);
     }
 
     // Test edge cases with ambigous values
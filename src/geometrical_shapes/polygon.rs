@@ -0,0 +1,199 @@
+use super::stroke::StrokeMask;
+use super::{Animatable, ClipRect, Displayable, Drawable, Point, Polygonal, Style, Styled};
+use raster::Color;
+
+/// Represents an arbitrary closed polygon with ordered vertices and a style
+/// Edges connect consecutive vertices, the last one wrapping to the first
+/// Optional holes are closed outlines cut out of the polygon's interior
+#[derive(Debug, Clone)]
+pub struct Polygon {
+    vertices: Vec<Point>,
+    holes: Vec<Vec<Point>>,
+    style: Style,
+}
+
+#[allow(dead_code)]
+impl Polygon {
+    /// Creates a new polygon from an ordered list of vertices, stroked in white
+    pub fn new(vertices: &[Point]) -> Self {
+        Polygon::from_vertices(vertices, Color::white())
+    }
+
+    /// Creates a polygon stroked 2px wide in an explicit color
+    pub fn from_vertices(vertices: &[Point], color: Color) -> Self {
+        Polygon {
+            vertices: vertices.to_vec(),
+            holes: Vec::new(),
+            style: Style::new().stroke(color).width(2),
+        }
+    }
+
+    /// Cuts the given outlines out of the polygon's interior
+    pub fn with_holes(mut self, holes: Vec<Vec<Point>>) -> Self {
+        self.holes = holes;
+        self
+    }
+
+    /// Returns the ordered vertices of the polygon
+    pub fn vertices(&self) -> &[Point] {
+        &self.vertices
+    }
+
+    /// Returns the outlines of the holes cut out of the polygon
+    pub fn holes(&self) -> &[Vec<Point>] {
+        &self.holes
+    }
+}
+
+impl Styled for Polygon {
+    fn style(&self) -> &Style {
+        &self.style
+    }
+
+    fn style_mut(&mut self) -> &mut Style {
+        &mut self.style
+    }
+}
+
+impl Animatable for Polygon {
+    /// Moves every vertex and blends the style, outlines with different vertex counts snap halfway
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        Polygon {
+            vertices: self.vertices.interpolate(&to.vertices, t),
+            holes: self.holes.interpolate(&to.holes, t),
+            style: self.style.interpolate(&to.style, t),
+        }
+    }
+}
+
+impl Drawable for Polygon {
+    /// Renders the polygon and hole outlines using line segments
+    /// Draws every edge with the style's width, each pixel once
+    fn draw(&self, image: &mut dyn Displayable) {
+        if !self.is_visible_on(image) {
+            return;
+        }
+
+        let rings: Vec<&[Point]> = std::iter::once(&self.vertices)
+            .chain(&self.holes)
+            .map(|ring| ring.as_slice())
+            .collect();
+        self.style.paint_fill(&rings, image);
+
+        let mut mask = StrokeMask::new(image).styled(&self.style);
+        for ring in rings {
+            mask.add_ring(ring, self.style.width);
+        }
+        self.style.paint_stroke(mask, image);
+    }
+
+    /// Returns the color of the polygon's style
+    fn color(&self) -> Color {
+        self.style.color()
+    }
+
+    /// Covers the outline and hole vertices, widened by the edges
+    fn bounding_box(&self) -> Option<ClipRect> {
+        ClipRect::around(self.vertices.iter().chain(self.holes.iter().flatten()))
+            .map(|area| area.expand(self.style.reach()))
+    }
+}
+
+impl Polygonal for Polygon {
+    /// A polygon flattens to itself
+    fn to_polygons(&self) -> Vec<Polygon> {
+        vec![self.clone()]
+    }
+}
+
+// Unit tests for Polygon
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometrical_shapes::golden::{self, Tolerance};
+    use raster::Image;
+
+    // Test polygon creation keeps vertex order
+    #[test]
+    fn test_polygon_new() {
+        let vertices = [Point::new(0, 0), Point::new(10, 0), Point::new(5, 8)];
+        let polygon = Polygon::new(&vertices);
+
+        assert_eq!(polygon.vertices(), &vertices);
+        let color = polygon.color();
+        assert_eq!((color.r, color.g, color.b), (255, 255, 255));
+    }
+
+    // Test polygon outline closes back to the first vertex
+    #[test]
+    fn test_polygon_draw() {
+        let vertices = [Point::new(2, 2), Point::new(12, 2), Point::new(12, 12)];
+        let color = Color::rgb(255, 255, 255);
+        let polygon = Polygon::from_vertices(&vertices, color.clone());
+
+        let mut image = Image::blank(20, 20);
+        polygon.draw(&mut image);
+
+        // The closing edge runs along the diagonal from (12, 12) to (2, 2)
+        for i in 2..=12 {
+            let pixel = image.get_pixel(i, i).unwrap();
+            assert_eq!(pixel.r, color.r);
+            assert_eq!(pixel.g, color.g);
+            assert_eq!(pixel.b, color.b);
+        }
+    }
+
+    // Test hole outlines are drawn along with the polygon
+    #[test]
+    fn test_polygon_holes() {
+        let outline = [
+            Point::new(0, 0),
+            Point::new(19, 0),
+            Point::new(19, 19),
+            Point::new(0, 19),
+        ];
+        let hole = vec![
+            Point::new(8, 8),
+            Point::new(12, 8),
+            Point::new(12, 12),
+            Point::new(8, 12),
+        ];
+        let polygon = Polygon::from_vertices(&outline, Color::white()).with_holes(vec![hole]);
+
+        assert_eq!(polygon.holes().len(), 1);
+
+        let mut image = Image::blank(20, 20);
+        polygon.draw(&mut image);
+        assert_eq!(image.get_pixel(10, 8).unwrap().r, 255);
+        assert_eq!(image.get_pixel(10, 10).unwrap().r, 0);
+    }
+
+    // Test drawing degenerate polygons does not panic
+    #[test]
+    fn test_polygon_edge_cases() {
+        let mut image = Image::blank(20, 20);
+
+        Polygon::new(&[]).draw(&mut image);
+        Polygon::new(&[Point::new(5, 5)]).draw(&mut image);
+        Polygon::new(&[Point::new(-50, -50), Point::new(100, 100)]).draw(&mut image);
+    }
+
+    // Test a concave polygon with a hole against its golden image
+    #[test]
+    fn test_polygon_golden() {
+        let outline = [
+            Point::new(5, 5),
+            Point::new(95, 10),
+            Point::new(60, 50),
+            Point::new(90, 95),
+            Point::new(10, 85),
+        ];
+        let hole = vec![Point::new(20, 30), Point::new(40, 30), Point::new(30, 60)];
+        let polygon = Polygon::new(&outline)
+            .with_holes(vec![hole])
+            .with_stroke(Color::rgb(255, 128, 0));
+
+        let image = golden::render(100, 100, &[&polygon]);
+        golden::assert_golden("polygon", &image, Tolerance::EXACT);
+    }
+}
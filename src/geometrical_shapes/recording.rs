@@ -0,0 +1,154 @@
+use super::{ClipRect, Displayable};
+use raster::Color;
+use std::collections::{HashMap, HashSet};
+
+/// Surface that logs every pixel write instead of rasterizing it
+/// Lets tests assert exactly which pixels a shape touches, and how often,
+/// without allocating a full image
+#[derive(Debug, Clone, Default)]
+pub struct Recording {
+    writes: Vec<(i32, i32, Color)>,
+    spans: Vec<(i32, i32, i32)>,
+    counts: HashMap<(i32, i32), usize>,
+    bounds: Option<ClipRect>,
+}
+
+#[allow(dead_code)]
+impl Recording {
+    /// Creates an unbounded recording surface
+    pub fn new() -> Self {
+        Recording::default()
+    }
+
+    /// Creates a recording surface that reports the given bounds,
+    /// so shapes clip and reject as they would on an image of that size
+    /// Writes outside the bounds are still logged
+    pub fn with_bounds(bounds: ClipRect) -> Self {
+        Recording {
+            bounds: Some(bounds),
+            ..Recording::default()
+        }
+    }
+
+    /// Every write in the order it was made
+    pub fn writes(&self) -> &[(i32, i32, Color)] {
+        &self.writes
+    }
+
+    /// Every `(left, right, y)` run written through `display_span`, in order
+    /// Its pixels are logged as writes too
+    pub fn spans(&self) -> &[(i32, i32, i32)] {
+        &self.spans
+    }
+
+    /// Number of times the pixel was written
+    pub fn count(&self, x: i32, y: i32) -> usize {
+        self.counts.get(&(x, y)).copied().unwrap_or(0)
+    }
+
+    /// Color of the last write to the pixel, if any
+    pub fn color_at(&self, x: i32, y: i32) -> Option<&Color> {
+        self.writes
+            .iter()
+            .rev()
+            .find(|(wx, wy, _)| (*wx, *wy) == (x, y))
+            .map(|(_, _, color)| color)
+    }
+
+    /// Pixels written at least once
+    pub fn touched(&self) -> HashSet<(i32, i32)> {
+        self.counts.keys().copied().collect()
+    }
+
+    /// Pixels written more than once
+    pub fn overdrawn(&self) -> HashSet<(i32, i32)> {
+        self.counts
+            .iter()
+            .filter(|&(_, &count)| count > 1)
+            .map(|(&pixel, _)| pixel)
+            .collect()
+    }
+
+    /// Number of redundant writes, i.e. writes beyond the first to each pixel
+    pub fn overdraw(&self) -> usize {
+        self.writes.len() - self.counts.len()
+    }
+
+    /// Forgets every write, keeping the bounds
+    pub fn clear(&mut self) {
+        self.writes.clear();
+        self.spans.clear();
+        self.counts.clear();
+    }
+}
+
+impl Displayable for Recording {
+    fn display(&mut self, x: i32, y: i32, color: Color) {
+        *self.counts.entry((x, y)).or_insert(0) += 1;
+        self.writes.push((x, y, color));
+    }
+
+    fn display_span(&mut self, left: i32, right: i32, y: i32, color: Color) {
+        if left <= right {
+            self.spans.push((left, right, y));
+        }
+        for x in left..=right {
+            self.display(x, y, color.clone());
+        }
+    }
+
+    fn bounds(&self) -> Option<ClipRect> {
+        self.bounds
+    }
+}
+
+// Unit tests for the recording surface
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometrical_shapes::{Drawable, Point};
+
+    // Test writes, counts and overdraw are tracked per pixel
+    #[test]
+    fn test_recording_overdraw() {
+        let mut recording = Recording::new();
+        recording.display(1, 1, Color::red());
+        recording.display(2, 1, Color::green());
+        recording.display(1, 1, Color::blue());
+
+        assert_eq!(recording.writes().len(), 3);
+        assert_eq!(recording.count(1, 1), 2);
+        assert_eq!(recording.count(5, 5), 0);
+        assert_eq!(recording.touched(), HashSet::from([(1, 1), (2, 1)]));
+        assert_eq!(recording.overdrawn(), HashSet::from([(1, 1)]));
+        assert_eq!(recording.overdraw(), 1);
+        assert_eq!(recording.color_at(1, 1).unwrap().b, 255);
+        assert!(recording.color_at(3, 3).is_none());
+
+        recording.clear();
+        assert!(recording.writes().is_empty());
+        assert!(recording.touched().is_empty());
+    }
+
+    // Test a point touches exactly its 3x3 block once each
+    #[test]
+    fn test_recording_point() {
+        let mut recording = Recording::new();
+        Point::new(10, 20).draw(&mut recording);
+
+        assert_eq!(recording.touched().len(), 9);
+        assert_eq!(recording.overdraw(), 0);
+        assert!(recording.touched().contains(&(9, 21)));
+    }
+
+    // Test reported bounds let shapes skip work entirely
+    #[test]
+    fn test_recording_bounds() {
+        let mut recording = Recording::with_bounds(ClipRect::from_size(10, 10));
+        Point::new(50, 50).draw(&mut recording);
+        assert!(recording.writes().is_empty());
+
+        Point::new(0, 0).draw(&mut recording);
+        assert_eq!(recording.writes().len(), 9);
+    }
+}
@@ -1,40 +1,39 @@
-use super::{Drawable, Line, Point};
+use super::stroke::StrokeMask;
+use super::{
+    Animatable, ClipRect, Displayable, Drawable, Point, Polygon, Polygonal, Style, Styled,
+};
 use rand::Rng;
-use raster::{Color, Image};
+use raster::Color;
 
-/// Represents a rectangle composed of two diagonal points and a color
+/// Represents a rectangle composed of two diagonal points, painted with a style
 /// Can draw itself as a bordered shape on an image
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Rectangle {
-    rects: Vec<(Point, Point, Color)>,
+    rects: Vec<(Point, Point)>,
+    style: Style,
 }
 
 #[allow(dead_code)]
 impl Rectangle {
     /// Creates a new rectangle from two given points
     /// Calculates width and height by summing coordinates
-    /// Assigns a random bright color (RGB 100–254)
+    /// Kept for compatibility, `from_corners` uses the points as given
     pub fn new(p1: &Point, p2: &Point) -> Self {
-        let mut rng = rand::thread_rng();
-        let mut rects = Vec::new();
-
-        let rect_height = p1.y + p2.y;
-        let rect_width = p2.x + p2.x;
+        let rect_height = p1.y.saturating_add(p2.y);
+        let rect_width = p2.x.saturating_add(p2.x);
 
         let top_left = Point::new(p2.x, p2.y);
-        let bottom_right = Point::new(top_left.x + rect_width, top_left.y + rect_height);
-        let color = Color::rgb(
-            rng.gen_range(100..255),
-            rng.gen_range(100..255),
-            rng.gen_range(100..255),
+        let bottom_right = Point::new(
+            top_left.x.saturating_add(rect_width),
+            top_left.y.saturating_add(rect_height),
         );
-
-        rects.push((top_left, bottom_right, color));
-        Rectangle { rects }
+        Rectangle::from_corners(&top_left, &bottom_right)
     }
 
     /// Generates a rectangle at a random position with random size and color
     /// Position is randomly chosen, size is within defined bounds
+    /// Ignores its arguments and places it in an 800x800 area,
+    /// kept for compatibility, see `random_in`
     pub fn random(_p1: &Point, _p2: &Point) -> Self {
         let mut rng = rand::thread_rng();
         let mut rects = Vec::new();
@@ -42,47 +41,136 @@ impl Rectangle {
         let width = rng.gen_range(100..250);
         let height = rng.gen_range(80..180);
         let pos = Point::random(800, 800);
-        let color = Color::rgb(
-            rng.gen_range(100..255),
-            rng.gen_range(100..255),
-            rng.gen_range(100..255),
+        rects.push((pos.clone(), Point::new(pos.x + width, pos.y + height)));
+
+        Rectangle {
+            rects,
+            style: Style::random(100..255).width(2),
+        }
+    }
+
+    /// Creates a rectangle between two opposite corners, in any order,
+    /// stroked 2px wide in white
+    pub fn from_corners(p1: &Point, p2: &Point) -> Self {
+        let top_left = Point::new(p1.x.min(p2.x), p1.y.min(p2.y));
+        let bottom_right = Point::new(p1.x.max(p2.x), p1.y.max(p2.y));
+
+        Rectangle {
+            rects: vec![(top_left, bottom_right)],
+            style: Style::new().width(2),
+        }
+    }
+
+    /// Creates a rectangle with its top-left corner at `origin`,
+    /// spanning `width` by `height` pixels
+    pub fn from_origin_size(origin: &Point, width: i32, height: i32) -> Self {
+        let corner = Point::new(
+            origin.x.saturating_add(width),
+            origin.y.saturating_add(height),
         );
-        rects.push((
-            pos.clone(),
-            Point::new(pos.x + width, pos.y + height),
-            color,
-        ));
+        Rectangle::from_corners(origin, &corner)
+    }
+
+    /// Creates a rectangle centered on `center`, spanning `width` by `height` pixels
+    pub fn from_center_size(center: &Point, width: i32, height: i32) -> Self {
+        let origin = Point::new(
+            center.x.saturating_sub(width / 2),
+            center.y.saturating_sub(height / 2),
+        );
+        Rectangle::from_origin_size(&origin, width, height)
+    }
 
-        Rectangle { rects }
+    /// Generates a rectangle of random position and size lying entirely
+    /// inside a `width` by `height` area, stroked 2px wide in white
+    /// Sizes are picked as in `random`, shrunk to fit small areas
+    pub fn random_in(width: i32, height: i32) -> Self {
+        let mut rng = rand::thread_rng();
+        let (max_x, max_y) = (
+            width.saturating_sub(1).max(0),
+            height.saturating_sub(1).max(0),
+        );
+
+        let rect_width = rng.gen_range(100..250).min(max_x);
+        let rect_height = rng.gen_range(80..180).min(max_y);
+        let origin = Point::new(
+            rng.gen_range(0..=max_x - rect_width),
+            rng.gen_range(0..=max_y - rect_height),
+        );
+        Rectangle::from_origin_size(&origin, rect_width, rect_height)
+    }
+
+    /// Returns the four corners of each rectangle, clockwise from `p1`
+    fn corners(p1: &Point, p2: &Point) -> [Point; 4] {
+        [
+            p1.clone(),
+            Point::new(p2.x, p1.y),
+            p2.clone(),
+            Point::new(p1.x, p2.y),
+        ]
+    }
+}
+
+impl Styled for Rectangle {
+    fn style(&self) -> &Style {
+        &self.style
+    }
+
+    fn style_mut(&mut self) -> &mut Style {
+        &mut self.style
+    }
+}
+
+impl Animatable for Rectangle {
+    /// Moves the corners of every rectangle and blends the style
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        Rectangle {
+            rects: self.rects.interpolate(&to.rects, t),
+            style: self.style.interpolate(&to.style, t),
+        }
     }
 }
 
 //
 impl Drawable for Rectangle {
     /// Draws each rectangle by rendering its edges as lines
-    /// Uses thickness of 2 pixels for each edge
-    fn draw(&self, image: &mut Image) {
-        let thickness = 2;
-
-        for (p1, p2, color) in &self.rects {
-            let top_right = Point::new(p2.x, p1.y);
-            let bottom_left = Point::new(p1.x, p2.y);
-
-            let top_edge = Line::from_points(p1, &top_right, thickness, color.clone());
-            let right_edge = Line::from_points(&top_right, p2, thickness, color.clone());
-            let bottom_edge = Line::from_points(p2, &bottom_left, thickness, color.clone());
-            let left_edge = Line::from_points(&bottom_left, p1, thickness, color.clone());
-
-            top_edge.draw(image);
-            right_edge.draw(image);
-            bottom_edge.draw(image);
-            left_edge.draw(image);
+    /// Fills the interior first if the style has a fill, corners are written once
+    fn draw(&self, image: &mut dyn Displayable) {
+        if !self.is_visible_on(image) {
+            return;
+        }
+
+        for (p1, p2) in &self.rects {
+            let corners = Rectangle::corners(p1, p2);
+            self.style.paint_fill(&[&corners], image);
+
+            let mut mask = StrokeMask::new(image).styled(&self.style);
+            mask.add_ring(&corners, self.style.width);
+            self.style.paint_stroke(mask, image);
         }
     }
 
-    /// Returns a fallback color for the rectangle
+    /// Returns the color of the rectangle's style
     fn color(&self) -> Color {
-        Color::black()
+        self.style.color()
+    }
+
+    /// Covers every rectangle's corners, widened by the edges
+    fn bounding_box(&self) -> Option<ClipRect> {
+        ClipRect::around(self.rects.iter().flat_map(|(p1, p2)| [p1, p2]))
+            .map(|area| area.expand(self.style.reach()))
+    }
+}
+
+impl Polygonal for Rectangle {
+    /// Flattens each rectangle into its four corners
+    fn to_polygons(&self) -> Vec<Polygon> {
+        self.rects
+            .iter()
+            .map(|(p1, p2)| {
+                Polygon::from_vertices(&Rectangle::corners(p1, p2), self.color())
+                    .with_style(self.style.clone())
+            })
+            .collect()
     }
 }
 
@@ -90,6 +178,8 @@ impl Drawable for Rectangle {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::geometrical_shapes::golden::{self, Tolerance};
+    use raster::Image;
 
     // Test basic rectangle creation and point placement
     #[test]
@@ -99,7 +189,7 @@ mod tests {
         let rect = Rectangle::new(&p1, &p2);
 
         assert_eq!(rect.rects.len(), 1);
-        let (top_left, bottom_right, _) = &rect.rects[0];
+        let (top_left, bottom_right) = &rect.rects[0];
         assert_eq!(top_left.x, 100);
         assert_eq!(top_left.y, 100);
         assert_eq!(bottom_right.x, 300); // 100 + (100 + 100)
@@ -114,7 +204,7 @@ mod tests {
         let rect = Rectangle::random(&p1, &p2);
 
         // Check each rectangle's properties
-        for (top_left, bottom_right, _) in &rect.rects {
+        for (top_left, bottom_right) in &rect.rects {
             assert!(top_left.x >= 0 && top_left.x <= 800);
             assert!(top_left.y >= 0 && top_left.y <= 800);
             assert!(bottom_right.x > top_left.x);
@@ -142,11 +232,29 @@ mod tests {
         let p2 = Point::new(100, 100);
         let rect = Rectangle::new(&p1, &p2);
 
-        let black = Color::black();
         let rect_color = rect.color();
-        assert_eq!(rect_color.r, black.r);
-        assert_eq!(rect_color.g, black.g);
-        assert_eq!(rect_color.b, black.b);
+        assert_eq!((rect_color.r, rect_color.g, rect_color.b), (255, 255, 255));
+
+        let red = Rectangle::new(&p1, &p2).with_stroke(Color::red()).color();
+        assert_eq!((red.r, red.g, red.b), (255, 0, 0));
+    }
+
+    // Test flattening yields the four corners in drawing order
+    #[test]
+    fn test_rectangle_to_polygons() {
+        let rect = Rectangle::new(&Point::new(0, 0), &Point::new(10, 20));
+        let polygons = rect.to_polygons();
+
+        assert_eq!(polygons.len(), 1);
+        assert_eq!(
+            polygons[0].vertices(),
+            &[
+                Point::new(10, 20),
+                Point::new(30, 20),
+                Point::new(30, 40),
+                Point::new(10, 40),
+            ]
+        );
     }
 
     // Test edge cases
@@ -170,4 +278,61 @@ mod tests {
         let rect = Rectangle::new(&p1, &p2);
         assert_eq!(rect.rects.len(), 1);
     }
+
+    // Test rectangle outlines against their golden image
+    #[test]
+    fn test_rectangle_golden() {
+        let rectangle =
+            Rectangle::new(&Point::new(10, 15), &Point::new(80, 60)).with_stroke(Color::red());
+        let flipped =
+            Rectangle::new(&Point::new(90, 90), &Point::new(40, 70)).with_stroke(Color::blue());
+
+        let image = golden::render(100, 100, &[&rectangle, &flipped]);
+        golden::assert_golden("rectangle", &image, Tolerance::EXACT);
+    }
+
+    // Test explicit constructors place corners exactly and never pick random colors
+    #[test]
+    fn test_rectangle_explicit() {
+        let corners = |rect: &Rectangle| {
+            let (p1, p2) = &rect.rects[0];
+            (p1.x, p1.y, p2.x, p2.y)
+        };
+
+        let from_corners = Rectangle::from_corners(&Point::new(50, 10), &Point::new(20, 40));
+        assert_eq!(corners(&from_corners), (20, 10, 50, 40));
+
+        let from_origin = Rectangle::from_origin_size(&Point::new(20, 10), 30, 30);
+        assert_eq!(corners(&from_origin), (20, 10, 50, 40));
+
+        let from_center = Rectangle::from_center_size(&Point::new(35, 25), 30, 30);
+        assert_eq!(corners(&from_center), (20, 10, 50, 40));
+
+        for rect in [from_corners, from_origin, from_center] {
+            let color = rect.color();
+            assert_eq!((color.r, color.g, color.b), (255, 255, 255));
+            assert_eq!(rect.style().width, 2);
+        }
+    }
+
+    // Test random rectangles stay inside the requested area, however small
+    #[test]
+    fn test_rectangle_random_in() {
+        for (width, height) in [
+            (300, 200),
+            (1000, 1000),
+            (50, 20),
+            (1, 1),
+            (0, -5),
+            (i32::MIN, i32::MIN),
+        ] {
+            for _ in 0..20 {
+                let rect = Rectangle::random_in(width, height);
+                let (top_left, bottom_right) = &rect.rects[0];
+                assert!(top_left.x >= 0 && top_left.y >= 0);
+                assert!(bottom_right.x <= width.saturating_sub(1).max(0));
+                assert!(bottom_right.y <= height.saturating_sub(1).max(0));
+            }
+        }
+    }
 }
@@ -0,0 +1,410 @@
+use super::line::rasterize_line;
+use super::{ClipRect, Dash, Displayable, LineCap, LineJoin, Point, Style};
+use raster::Color;
+
+/// Miter joins longer than this many half widths are drawn as bevels instead
+pub(super) const MITER_LIMIT: f64 = 4.0;
+
+/// Coverage mask collecting every pixel of a shape's outline
+/// Edges meeting at corners or crossing each other cover some pixels twice,
+/// the mask merges them so each pixel is written exactly once when painted
+/// An optional dash pattern is measured along everything added so far,
+/// so dashes carry on from one edge to the next
+pub(super) struct StrokeMask {
+    bounds: Option<ClipRect>,
+    pixels: Vec<(i32, i32)>,
+    dash: Option<Dash>,
+    cap: LineCap,
+    join: LineJoin,
+    distance: f64,
+}
+
+impl StrokeMask {
+    /// Creates an empty mask that drops pixels the surface can never display
+    pub(super) fn new(image: &dyn Displayable) -> Self {
+        StrokeMask {
+            bounds: image.bounds(),
+            pixels: Vec::new(),
+            dash: None,
+            cap: LineCap::default(),
+            join: LineJoin::default(),
+            distance: 0.0,
+        }
+    }
+
+    /// Takes the dash pattern, caps and joins from `style`
+    pub(super) fn styled(mut self, style: &Style) -> Self {
+        self.dash = style.dash.clone();
+        self.cap = style.cap;
+        self.join = style.join;
+        self
+    }
+
+    /// Marks a pixel lying `distance` pixels along the outline,
+    /// unless it falls in a gap of the dash pattern
+    pub(super) fn add_at(&mut self, x: i32, y: i32, distance: f64) {
+        if self.dash.as_ref().is_none_or(|dash| dash.is_on(distance)) {
+            self.add(x, y);
+        }
+    }
+
+    /// Marks a single pixel as covered
+    pub(super) fn add(&mut self, x: i32, y: i32) {
+        if self.bounds.is_none_or(|b| b.contains(x, y)) {
+            // Stored row first so painting walks the surface in row-major order
+            self.pixels.push((y, x));
+        }
+    }
+
+    /// Marks every pixel of a line of the given thickness
+    /// Dashes are measured by projecting each pixel onto the line,
+    /// continuing from where the previous line left off
+    pub(super) fn add_line(&mut self, start: &Point, end: &Point, thickness: i32) {
+        let bounds = self.bounds;
+        let (x0, y0) = (start.x as f64, start.y as f64);
+        let (dx, dy) = (end.x as f64 - x0, end.y as f64 - y0);
+        let length = dx.hypot(dy);
+        let offset = self.distance;
+
+        rasterize_line(start, end, thickness, bounds, &mut |x, y| {
+            let along = if length > 0.0 {
+                ((x as f64 - x0) * dx + (y as f64 - y0) * dy) / length
+            } else {
+                0.0
+            };
+            self.add_at(x, y, offset + along.clamp(0.0, length));
+        });
+        self.distance += length;
+    }
+
+    /// Marks every edge of a closed outline, the last vertex joining the first
+    /// The dash pattern starts over at the first vertex
+    pub(super) fn add_ring(&mut self, ring: &[Point], thickness: i32) {
+        self.distance = 0.0;
+        self.add_path(ring, thickness, true);
+    }
+
+    /// Marks every edge of a path, with joins between consecutive edges
+    /// and, unless the path is closed, caps on both ends
+    /// Strokes up to 2px wide already meet at their vertices, so only wider
+    /// strokes get joins and caps
+    pub(super) fn add_path(&mut self, path: &[Point], thickness: i32, closed: bool) {
+        let edges = if closed {
+            path.len()
+        } else {
+            path.len().saturating_sub(1)
+        };
+        let start = self.distance;
+        let mut corners = Vec::with_capacity(path.len());
+        for i in 0..edges {
+            corners.push(self.distance);
+            self.add_line(&path[i], &path[(i + 1) % path.len()], thickness);
+        }
+        corners.push(self.distance);
+
+        let half = thickness as f64 / 2.0;
+        if thickness <= 2 || path.len() < 2 {
+            return;
+        }
+
+        let vertex = |i: usize| (path[i].x as f64, path[i].y as f64);
+        let joined = if closed {
+            0..path.len()
+        } else {
+            1..path.len() - 1
+        };
+        for i in joined {
+            let previous = vertex((i + path.len() - 1) % path.len());
+            let next = vertex((i + 1) % path.len());
+            let distance = if i == 0 { start } else { corners[i] };
+            self.add_join(previous, vertex(i), next, half, distance);
+        }
+
+        if !closed {
+            let last = path.len() - 1;
+            self.add_cap(vertex(1), vertex(0), half, start);
+            self.add_cap(vertex(last - 1), vertex(last), half, self.distance);
+        }
+    }
+
+    /// Covers the outer corner at `vertex` between the edges coming from
+    /// `previous` and going on to `next`
+    fn add_join(
+        &mut self,
+        previous: (f64, f64),
+        vertex: (f64, f64),
+        next: (f64, f64),
+        half: f64,
+        distance: f64,
+    ) {
+        let (Some(d1), Some(d2)) = (direction(previous, vertex), direction(vertex, next)) else {
+            return;
+        };
+        let turn = d1.0 * d2.1 - d1.1 * d2.0;
+        if turn.abs() < 1e-9 {
+            return;
+        }
+
+        // Normals pointing away from the inside of the turn
+        let side = -turn.signum();
+        let n1 = (-d1.1 * side, d1.0 * side);
+        let n2 = (-d2.1 * side, d2.0 * side);
+        let at = |n: (f64, f64), length: f64| (vertex.0 + n.0 * length, vertex.1 + n.1 * length);
+
+        match self.join {
+            LineJoin::Round => self.add_disc(vertex, half, distance),
+            LineJoin::Bevel => self.add_convex(&[vertex, at(n1, half), at(n2, half)], distance),
+            LineJoin::Miter => {
+                let (mx, my) = (n1.0 + n2.0, n1.1 + n2.1);
+                let norm = mx.hypot(my);
+                let cos = (mx * n1.0 + my * n1.1) / norm;
+                if norm < 1e-9 || 1.0 / cos > MITER_LIMIT {
+                    self.add_convex(&[vertex, at(n1, half), at(n2, half)], distance);
+                } else {
+                    let tip = at((mx / norm, my / norm), half / cos);
+                    self.add_convex(&[vertex, at(n1, half), tip, at(n2, half)], distance);
+                }
+            }
+        }
+    }
+
+    /// Covers the cap at `end` of the edge coming from `from`
+    fn add_cap(&mut self, from: (f64, f64), end: (f64, f64), half: f64, distance: f64) {
+        let Some(d) = direction(from, end) else {
+            return;
+        };
+        let n = (-d.1 * half, d.0 * half);
+        let ahead = (end.0 + d.0 * half, end.1 + d.1 * half);
+
+        match self.cap {
+            LineCap::Butt => {}
+            LineCap::Round => self.add_disc(end, half, distance),
+            LineCap::Square => self.add_convex(
+                &[
+                    (end.0 + n.0, end.1 + n.1),
+                    (ahead.0 + n.0, ahead.1 + n.1),
+                    (ahead.0 - n.0, ahead.1 - n.1),
+                    (end.0 - n.0, end.1 - n.1),
+                ],
+                distance,
+            ),
+        }
+    }
+
+    /// Marks every pixel within `radius` of `center`
+    fn add_disc(&mut self, center: (f64, f64), radius: f64, distance: f64) {
+        let (left, top) = (center.0 - radius, center.1 - radius);
+        let (right, bottom) = (center.0 + radius, center.1 + radius);
+        for (x, y) in self.area(left, top, right, bottom) {
+            if (x as f64 - center.0).hypot(y as f64 - center.1) <= radius {
+                self.add_pixel(x, y, distance);
+            }
+        }
+    }
+
+    /// Marks every pixel inside a convex polygon, edges included
+    fn add_convex(&mut self, polygon: &[(f64, f64)], distance: f64) {
+        let (mut left, mut top) = (f64::MAX, f64::MAX);
+        let (mut right, mut bottom) = (f64::MIN, f64::MIN);
+        for &(x, y) in polygon {
+            (left, right) = (left.min(x), right.max(x));
+            (top, bottom) = (top.min(y), bottom.max(y));
+        }
+
+        // Winding of the polygon, so the inside test works either way round
+        let area: f64 = (0..polygon.len())
+            .map(|i| {
+                let (a, b) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+                a.0 * b.1 - b.0 * a.1
+            })
+            .sum();
+        let sign = area.signum();
+
+        for (x, y) in self.area(left, top, right, bottom) {
+            let inside = (0..polygon.len()).all(|i| {
+                let (a, b) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+                let cross = (b.0 - a.0) * (y as f64 - a.1) - (b.1 - a.1) * (x as f64 - a.0);
+                cross * sign >= -1e-9
+            });
+            if inside {
+                self.add_pixel(x, y, distance);
+            }
+        }
+    }
+
+    /// Pixels inside the given area that the surface can display
+    fn area(&self, left: f64, top: f64, right: f64, bottom: f64) -> Vec<(i64, i64)> {
+        let (mut left, mut top) = (left.ceil() as i64, top.ceil() as i64);
+        let (mut right, mut bottom) = (right.floor() as i64, bottom.floor() as i64);
+        if let Some(b) = self.bounds {
+            (left, top) = (left.max(b.left as i64), top.max(b.top as i64));
+            (right, bottom) = (right.min(b.right as i64), bottom.min(b.bottom as i64));
+        }
+        (top..=bottom)
+            .flat_map(|y| (left..=right).map(move |x| (x, y)))
+            .collect()
+    }
+
+    /// Marks a pixel given in wide coordinates, skipping it past the edge
+    /// of the coordinate space
+    fn add_pixel(&mut self, x: i64, y: i64, distance: f64) {
+        if let (Ok(x), Ok(y)) = (i32::try_from(x), i32::try_from(y)) {
+            self.add_at(x, y, distance);
+        }
+    }
+
+    /// Writes each covered pixel to the surface exactly once,
+    /// neighbouring pixels of a row as a single span
+    pub(super) fn paint(mut self, image: &mut dyn Displayable, color: &Color) {
+        self.pixels.sort_unstable();
+        self.pixels.dedup();
+
+        let mut pixels = self.pixels.into_iter().peekable();
+        while let Some((y, left)) = pixels.next() {
+            let mut right = left;
+            while let Some((_, x)) =
+                pixels.next_if(|&(ny, nx)| ny == y && Some(nx) == right.checked_add(1))
+            {
+                right = x;
+            }
+            image.display_span(left, right, y, color.clone());
+        }
+    }
+}
+
+/// Unit vector pointing from `a` to `b`, if they are apart
+fn direction(a: (f64, f64), b: (f64, f64)) -> Option<(f64, f64)> {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length = dx.hypot(dy);
+    (length > 0.0).then(|| (dx / length, dy / length))
+}
+
+// Unit tests for stroke masks
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometrical_shapes::Recording;
+
+    fn points(coords: &[(i32, i32)]) -> Vec<Point> {
+        coords.iter().map(|&(x, y)| Point::new(x, y)).collect()
+    }
+
+    // Test corners shared by consecutive edges are painted once
+    #[test]
+    fn test_stroke_ring_corners() {
+        let mut recording = Recording::new();
+        let mut mask = StrokeMask::new(&recording);
+        mask.add_ring(&points(&[(0, 0), (10, 0), (10, 10), (0, 10)]), 2);
+        mask.paint(&mut recording, &Color::white());
+
+        assert_eq!(recording.overdraw(), 0);
+        assert_eq!(recording.count(0, 0), 1);
+        assert_eq!(recording.count(10, 10), 1);
+    }
+
+    // Test crossing lines are merged into one coverage
+    #[test]
+    fn test_stroke_crossing_lines() {
+        let mut recording = Recording::new();
+        let mut mask = StrokeMask::new(&recording);
+        mask.add_line(&Point::new(0, 5), &Point::new(10, 5), 3);
+        mask.add_line(&Point::new(5, 0), &Point::new(5, 10), 3);
+        mask.paint(&mut recording, &Color::white());
+
+        assert_eq!(recording.overdraw(), 0);
+        assert_eq!(recording.touched().len(), 2 * 33 - 9);
+    }
+
+    // Test pixels outside the surface bounds are dropped before painting
+    #[test]
+    fn test_stroke_bounds() {
+        let mut recording = Recording::with_bounds(ClipRect::from_size(5, 5));
+        let mut mask = StrokeMask::new(&recording);
+        mask.add_line(&Point::new(-100, 2), &Point::new(100, 2), 1);
+        mask.add(50, 50);
+        mask.paint(&mut recording, &Color::white());
+
+        assert_eq!(recording.touched(), (0..5).map(|x| (x, 2)).collect());
+        assert_eq!(recording.writes()[0].0, 0);
+    }
+
+    // Test dashes carry on around a corner instead of restarting
+    #[test]
+    fn test_stroke_dash_corners() {
+        let mut recording = Recording::new();
+        let dash = Dash::new(&[6.0, 2.0], 0.0);
+        let mut mask = StrokeMask::new(&recording).styled(&Style::new().dash(dash));
+        mask.add_ring(&points(&[(0, 0), (10, 0), (10, 10), (0, 10)]), 1);
+        mask.paint(&mut recording, &Color::white());
+
+        // The dash starting at (8, 0) turns the corner and ends at (10, 3)
+        for pixel in [(5, 0), (8, 0), (10, 0), (10, 3), (10, 6)] {
+            assert!(recording.touched().contains(&pixel), "{pixel:?}");
+        }
+        for pixel in [(6, 0), (7, 0), (10, 4), (10, 5)] {
+            assert!(!recording.touched().contains(&pixel), "{pixel:?}");
+        }
+        assert_eq!(recording.overdraw(), 0);
+    }
+
+    fn wide(style: Style, path: &[(i32, i32)], closed: bool) -> Recording {
+        let mut recording = Recording::new();
+        let mut mask = StrokeMask::new(&recording).styled(&style);
+        mask.add_path(&points(path), 5, closed);
+        mask.paint(&mut recording, &Color::white());
+        recording
+    }
+
+    // Test each join shape covers its own part of the outer corner
+    #[test]
+    fn test_stroke_joins() {
+        let path = [(0, 0), (20, 0), (20, 20)];
+        let miter = wide(Style::new().join(LineJoin::Miter), &path, false).touched();
+        let bevel = wide(Style::new().join(LineJoin::Bevel), &path, false).touched();
+        let round = wide(Style::new().join(LineJoin::Round), &path, false).touched();
+
+        // Neither edge reaches past the corner on its own
+        assert!(!bevel.contains(&(22, -1)));
+
+        assert!(miter.contains(&(22, -2)));
+        assert!(bevel.contains(&(21, -1)) && !bevel.contains(&(22, -2)));
+        assert!(round.contains(&(22, -1)) && !round.contains(&(22, -2)));
+        assert!(bevel.is_subset(&round) && round.is_subset(&miter));
+
+        // Nothing reaches past the miter tip at (22.5, -2.5)
+        assert_eq!(
+            miter.len(),
+            miter.iter().filter(|&&(x, y)| x < 23 && y > -3).count()
+        );
+    }
+
+    // Test very sharp corners fall back to a bevel past the miter limit
+    #[test]
+    fn test_stroke_miter_limit() {
+        let path = [(0, 0), (40, 0), (0, 2)];
+        let miter = wide(Style::new().join(LineJoin::Miter), &path, false).touched();
+        let bevel = wide(Style::new().join(LineJoin::Bevel), &path, false).touched();
+        assert_eq!(miter, bevel);
+    }
+
+    // Test caps extend open paths past their end points
+    #[test]
+    fn test_stroke_caps() {
+        let path = [(0, 0), (10, 0)];
+        let butt = wide(Style::new(), &path, false).touched();
+        let square = wide(Style::new().cap(LineCap::Square), &path, false).touched();
+        let round = wide(Style::new().cap(LineCap::Round), &path, false).touched();
+
+        assert!(butt.iter().all(|&(x, _)| (0..=10).contains(&x)));
+        assert!(square.contains(&(12, 2)) && square.contains(&(-2, -2)));
+        assert!(round.contains(&(12, 0)) && !round.contains(&(12, 2)));
+        assert!(butt.is_subset(&round) && round.is_subset(&square));
+
+        // Closed outlines have no ends to cap
+        let ring = [(0, 0), (10, 0), (10, 10)];
+        assert_eq!(
+            wide(Style::new().cap(LineCap::Square), &ring, true).touched(),
+            wide(Style::new(), &ring, true).touched()
+        );
+    }
+}
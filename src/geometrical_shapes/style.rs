@@ -0,0 +1,419 @@
+use super::fill::fill_rings;
+use super::stroke::{MITER_LIMIT, StrokeMask};
+use super::tween::snap;
+use super::{Animatable, Dash, Displayable, Paint, Painted, Point};
+use rand::Rng;
+use raster::Color;
+use std::ops::Range;
+
+/// Shape of the ends of open strokes
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineCap {
+    /// The stroke stops exactly at the end point
+    #[default]
+    Butt,
+    /// The stroke runs on by half its width past the end point
+    Square,
+    /// A half disc is added around the end point
+    Round,
+}
+
+/// Shape of the corners where two edges of a stroke meet
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineJoin {
+    /// Outer edges are extended to a sharp point,
+    /// falling back to a bevel for very sharp angles
+    #[default]
+    Miter,
+    /// The outer corner is cut off straight
+    Bevel,
+    /// The outer corner is rounded off
+    Round,
+}
+
+/// How a shape is painted, kept apart from its geometry
+/// Shapes stroke their outline with `stroke` and fill their interior with `fill`,
+/// either can be left out
+#[derive(Debug, Clone)]
+pub struct Style {
+    pub stroke: Option<Paint>,
+    pub width: i32,
+    pub dash: Option<Dash>,
+    pub cap: LineCap,
+    pub join: LineJoin,
+    pub fill: Option<Paint>,
+    pub opacity: f64,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Style::new()
+    }
+}
+
+#[allow(dead_code)]
+impl Style {
+    /// Creates a solid 1px white stroke with no fill
+    pub fn new() -> Self {
+        Style {
+            stroke: Some(Paint::Solid(Color::white())),
+            width: 1,
+            dash: None,
+            cap: LineCap::default(),
+            join: LineJoin::default(),
+            fill: None,
+            opacity: 1.0,
+        }
+    }
+
+    /// Creates a 1px stroke in a random color,
+    /// each channel picked from the given range
+    pub fn random(channels: Range<u8>) -> Self {
+        let mut rng = rand::thread_rng();
+        Style::new().stroke(Color::rgb(
+            rng.gen_range(channels.clone()),
+            rng.gen_range(channels.clone()),
+            rng.gen_range(channels),
+        ))
+    }
+
+    /// Sets the paint of the outline
+    pub fn stroke(mut self, paint: impl Into<Paint>) -> Self {
+        self.stroke = Some(paint.into());
+        self
+    }
+
+    /// Leaves the outline out
+    pub fn no_stroke(mut self) -> Self {
+        self.stroke = None;
+        self
+    }
+
+    /// Sets the outline width in pixels
+    pub fn width(mut self, width: i32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Dashes the outline
+    pub fn dash(mut self, dash: Dash) -> Self {
+        self.dash = Some(dash);
+        self
+    }
+
+    /// Sets the shape of open stroke ends
+    pub fn cap(mut self, cap: LineCap) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    /// Sets the shape of stroke corners
+    pub fn join(mut self, join: LineJoin) -> Self {
+        self.join = join;
+        self
+    }
+
+    /// Sets the paint of the interior
+    pub fn fill(mut self, paint: impl Into<Paint>) -> Self {
+        self.fill = Some(paint.into());
+        self
+    }
+
+    /// Sets the opacity from 0.0 to 1.0, scaling the alpha of every painted pixel
+    pub fn opacity(mut self, opacity: f64) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Main color of the style: the stroke color if it is solid,
+    /// then the fill color if that is solid, black otherwise
+    pub fn color(&self) -> Color {
+        [&self.stroke, &self.fill]
+            .into_iter()
+            .find_map(|paint| match paint {
+                Some(Paint::Solid(color)) => Some(color.clone()),
+                _ => None,
+            })
+            .unwrap_or_else(Color::black)
+    }
+
+    /// How far the stroke can spread past the shape's geometry, in pixels,
+    /// for growing bounding boxes
+    pub fn reach(&self) -> i32 {
+        if self.width <= 2 {
+            self.width.max(0) / 2
+        } else {
+            (self.width as f64 / 2.0 * MITER_LIMIT).ceil() as i32
+        }
+    }
+
+    /// Paints every pixel of the mask with the stroke paint
+    pub(super) fn paint_stroke(&self, mask: StrokeMask, image: &mut dyn Displayable) {
+        if let Some(paint) = &self.stroke {
+            let mut target = Painted::new(image, paint).with_opacity(self.opacity);
+            mask.paint(&mut target, &self.color());
+        }
+    }
+
+    /// Fills the region enclosed by `rings` with the fill paint
+    pub(super) fn paint_fill(&self, rings: &[&[Point]], image: &mut dyn Displayable) {
+        if let Some(paint) = &self.fill {
+            let mut target = Painted::new(image, paint).with_opacity(self.opacity);
+            fill_rings(rings, &self.color(), &mut target);
+        }
+    }
+}
+
+/// Blends solid paints, other paints cannot be blended and snap halfway
+fn interpolate_paint(from: &Option<Paint>, to: &Option<Paint>, t: f64) -> Option<Paint> {
+    match (from, to) {
+        (Some(Paint::Solid(a)), Some(Paint::Solid(b))) => Some(Paint::Solid(a.interpolate(b, t))),
+        _ => snap(from, to, t),
+    }
+}
+
+impl Animatable for Style {
+    /// Blends solid colors, width and opacity, everything else snaps halfway
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        let snapped = snap(self, to, t);
+        Style {
+            stroke: interpolate_paint(&self.stroke, &to.stroke, t),
+            width: self.width.interpolate(&to.width, t).max(0),
+            fill: interpolate_paint(&self.fill, &to.fill, t),
+            opacity: self.opacity.interpolate(&to.opacity, t).clamp(0.0, 1.0),
+            ..snapped
+        }
+    }
+}
+
+/// Shapes painted with a `Style`, set up through builder methods
+#[allow(dead_code)]
+pub trait Styled: Sized {
+    fn style(&self) -> &Style;
+    fn style_mut(&mut self) -> &mut Style;
+
+    /// Replaces the whole style
+    fn with_style(mut self, style: Style) -> Self {
+        *self.style_mut() = style;
+        self
+    }
+
+    /// Strokes the outline with the given color or paint
+    fn with_stroke(mut self, paint: impl Into<Paint>) -> Self {
+        self.style_mut().stroke = Some(paint.into());
+        self
+    }
+
+    /// Sets the outline width in pixels
+    fn with_width(mut self, width: i32) -> Self {
+        self.style_mut().width = width;
+        self
+    }
+
+    /// Strokes the outline with a dash pattern instead of a solid line
+    fn with_dash(mut self, dash: Dash) -> Self {
+        self.style_mut().dash = Some(dash);
+        self
+    }
+
+    /// Sets the shape of open stroke ends
+    fn with_cap(mut self, cap: LineCap) -> Self {
+        self.style_mut().cap = cap;
+        self
+    }
+
+    /// Sets the shape of stroke corners
+    fn with_join(mut self, join: LineJoin) -> Self {
+        self.style_mut().join = join;
+        self
+    }
+
+    /// Fills the interior with the given color or paint
+    fn with_fill(mut self, paint: impl Into<Paint>) -> Self {
+        self.style_mut().fill = Some(paint.into());
+        self
+    }
+
+    /// Sets the opacity from 0.0 to 1.0
+    fn with_opacity(mut self, opacity: f64) -> Self {
+        self.style_mut().opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+}
+
+// Unit tests for styles
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometrical_shapes::golden::{self, Tolerance};
+    use crate::geometrical_shapes::{
+        Circle, Cubes, Drawable, Gradient, Line, Pattern, Pentagon, Polygon, Recording, Triangle,
+    };
+
+    fn square() -> Polygon {
+        let corners = [
+            Point::new(0, 0),
+            Point::new(10, 0),
+            Point::new(10, 10),
+            Point::new(0, 10),
+        ];
+        Polygon::from_vertices(&corners, Color::white())
+    }
+
+    // Test builder methods set every field and opacity stays in range
+    #[test]
+    fn test_style_builder() {
+        let style = Style::new()
+            .stroke(Color::red())
+            .width(4)
+            .dash(Dash::dotted(2.0))
+            .cap(LineCap::Round)
+            .join(LineJoin::Bevel)
+            .fill(Color::blue())
+            .opacity(1.5);
+
+        assert_eq!(style.width, 4);
+        assert_eq!(style.dash, Some(Dash::dotted(2.0)));
+        assert_eq!((style.cap, style.join), (LineCap::Round, LineJoin::Bevel));
+        assert!(matches!(style.fill, Some(Paint::Solid(_))));
+        assert_eq!(style.opacity, 1.0);
+        assert_eq!(Style::new().opacity(-1.0).opacity, 0.0);
+    }
+
+    // Test the main color falls back from stroke to fill to black
+    #[test]
+    fn test_style_color() {
+        assert_eq!(Style::new().stroke(Color::red()).color().r, 255);
+
+        let filled = Style::new().no_stroke().fill(Color::green());
+        assert_eq!(filled.color().g, 255);
+
+        let gradient = Paint::linear(
+            &Point::new(0, 0),
+            &Point::new(10, 0),
+            Gradient::between(Color::red(), Color::blue()),
+        );
+        let painted = Style::new().stroke(gradient.clone()).fill(gradient);
+        let black = painted.color();
+        assert_eq!((black.r, black.g, black.b), (0, 0, 0));
+    }
+
+    // Test shapes built with an explicit style draw the same pixels every time
+    #[test]
+    fn test_style_deterministic() {
+        let draw = || {
+            let mut recording = Recording::new();
+            let style = Style::new().stroke(Color::rgb(10, 20, 30)).width(3);
+            Circle::new(&Point::new(20, 20), 10)
+                .with_style(style.clone())
+                .draw(&mut recording);
+            Line::new(&Point::new(0, 0), &Point::new(30, 5))
+                .with_style(style)
+                .draw(&mut recording);
+            recording
+                .writes()
+                .iter()
+                .map(|(x, y, c)| (*x, *y, c.r, c.g, c.b))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(draw(), draw());
+    }
+
+    // Test fills cover the interior and strokes can be left out
+    #[test]
+    fn test_style_fill() {
+        let mut recording = Recording::new();
+        square()
+            .with_style(Style::new().no_stroke().fill(Color::red()))
+            .draw(&mut recording);
+        assert_eq!(recording.touched().len(), 100);
+        assert_eq!(recording.color_at(5, 5).unwrap().r, 255);
+
+        // The stroke is painted over the fill
+        let mut recording = Recording::new();
+        square()
+            .with_style(Style::new().stroke(Color::white()).fill(Color::red()))
+            .draw(&mut recording);
+        assert_eq!(recording.color_at(0, 5).unwrap().g, 255);
+        assert_eq!(recording.color_at(5, 5).unwrap().g, 0);
+
+        // Nothing to paint at all
+        let mut recording = Recording::new();
+        square()
+            .with_style(Style::new().no_stroke())
+            .draw(&mut recording);
+        assert!(recording.writes().is_empty());
+    }
+
+    // Test opacity scales the alpha of strokes and fills
+    #[test]
+    fn test_style_opacity() {
+        let mut recording = Recording::new();
+        square()
+            .with_stroke(Color::white())
+            .with_fill(Color::red())
+            .with_opacity(0.5)
+            .draw(&mut recording);
+
+        assert_eq!(recording.color_at(0, 5).unwrap().a, 128);
+        assert_eq!(recording.color_at(5, 5).unwrap().a, 128);
+    }
+
+    // Test styled shapes of every kind against their golden image
+    #[test]
+    fn test_style_golden() {
+        let mut image = raster::Image::blank(200, 140);
+        let hatch = Pattern::hatch(std::f64::consts::FRAC_PI_4, 5, Color::rgb(255, 200, 0));
+
+        let joins = [LineJoin::Miter, LineJoin::Bevel, LineJoin::Round];
+        for (i, join) in joins.into_iter().enumerate() {
+            let x = 15 + 60 * i as i32;
+            Triangle::new(
+                &Point::new(x, 50),
+                &Point::new(x + 40, 50),
+                &Point::new(x + 10, 10),
+            )
+            .with_style(
+                Style::new()
+                    .stroke(Color::white())
+                    .width(7)
+                    .join(join)
+                    .fill(Color::rgb(0, 90, 160)),
+            )
+            .draw(&mut image);
+        }
+
+        let caps = [LineCap::Butt, LineCap::Square, LineCap::Round];
+        for (i, cap) in caps.into_iter().enumerate() {
+            let y = 75 + 12 * i as i32;
+            Line::from_points(&Point::new(15, y), &Point::new(60, y), 7, Color::white())
+                .with_cap(cap)
+                .draw(&mut image);
+        }
+
+        Circle::new(&Point::new(105, 100), 25)
+            .with_style(Style::new().stroke(Color::white()).width(3).fill(hatch))
+            .draw(&mut image);
+        Pentagon::new(&Point::new(165, 100), 25)
+            .with_style(
+                Style::new()
+                    .stroke(Color::rgb(255, 80, 80))
+                    .width(4)
+                    .join(LineJoin::Round)
+                    .dash(Dash::new(&[10.0, 6.0], 0.0)),
+            )
+            .draw(&mut image);
+        Cubes::new(&Point::new(40, 124), 14)
+            .with_style(
+                Style::new()
+                    .stroke(Color::white())
+                    .cap(LineCap::Round)
+                    .fill(Color::rgb(80, 80, 80)),
+            )
+            .draw(&mut image);
+
+        golden::assert_golden("style", &image, Tolerance::EXACT);
+    }
+}
@@ -0,0 +1,245 @@
+use super::Canvas;
+use std::collections::VecDeque;
+use std::f64::consts::PI;
+
+/// Filters a supersampled canvas is shrunk back to its target size with
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Downsample {
+    /// Plain mean of the samples covering each pixel
+    #[default]
+    Box,
+    /// Three-lobed Lanczos window, sharper edges at the cost of faint ringing
+    Lanczos,
+}
+
+/// Scene-wide anti-aliasing by drawing at `factor` times the resolution
+/// and filtering back down, so every shape gets smooth edges as it is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Supersample {
+    factor: i32,
+    filter: Downsample,
+}
+
+/// Lanczos kernel with three lobes
+fn lanczos(x: f64) -> f64 {
+    const LOBES: f64 = 3.0;
+    if x == 0.0 {
+        1.0
+    } else if x.abs() >= LOBES {
+        0.0
+    } else {
+        let px = PI * x;
+        LOBES * px.sin() * (px / LOBES).sin() / (px * px)
+    }
+}
+
+#[allow(dead_code)]
+impl Supersample {
+    /// Supersamples `factor` times along each axis, at least once
+    pub fn new(factor: i32) -> Self {
+        Supersample {
+            factor: factor.max(1),
+            filter: Downsample::default(),
+        }
+    }
+
+    pub fn with_filter(mut self, filter: Downsample) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    pub fn factor(&self) -> i32 {
+        self.factor
+    }
+
+    pub fn filter(&self) -> Downsample {
+        self.filter
+    }
+
+    /// Renders a `width` x `height` canvas by calling `draw` on a black canvas
+    /// `factor` times larger, with the factor to scale the scene's coordinates
+    /// and stroke widths by, then downsampling the result
+    pub fn render(&self, width: i32, height: i32, draw: impl FnOnce(&mut Canvas, i32)) -> Canvas {
+        let mut large = Canvas::new(
+            width.max(0).saturating_mul(self.factor),
+            height.max(0).saturating_mul(self.factor),
+        );
+        draw(&mut large, self.factor);
+        self.downsample(&large)
+    }
+
+    /// Sources and normalized weights making up each of `size` output pixels
+    /// along one axis of `source` samples
+    fn weights(&self, size: usize, source: usize) -> Vec<Vec<(usize, f64)>> {
+        let factor = self.factor as f64;
+        (0..size)
+            .map(|output| {
+                let taps: Vec<(usize, f64)> = match self.filter {
+                    Downsample::Box => {
+                        let start = output * self.factor as usize;
+                        (start..start + self.factor as usize)
+                            .map(|sample| (sample, 1.0))
+                            .collect()
+                    }
+                    Downsample::Lanczos => {
+                        let center = (output as f64 + 0.5) * factor;
+                        let reach = 3.0 * factor;
+                        let first = (center - reach).floor().max(0.0) as usize;
+                        let last = ((center + reach).ceil() as usize).min(source);
+                        (first..last)
+                            .map(|sample| {
+                                (sample, lanczos((sample as f64 + 0.5 - center) / factor))
+                            })
+                            .filter(|&(_, weight)| weight != 0.0)
+                            .collect()
+                    }
+                };
+                let total: f64 = taps.iter().map(|&(_, weight)| weight).sum();
+                taps.into_iter()
+                    .map(|(sample, weight)| (sample, weight / total))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Shrinks a canvas by the factor along each axis, dropping leftover samples
+    /// Channels are filtered separately, rows first, then columns
+    /// Only the band of filtered sample rows the current output row reads is kept,
+    /// a few rows for `Box` and six times the factor for `Lanczos`
+    pub fn downsample(&self, large: &Canvas) -> Canvas {
+        let width = (large.width() / self.factor) as usize;
+        let height = (large.height() / self.factor) as usize;
+        let mut canvas = Canvas::new(width as i32, height as i32);
+        if width == 0 || height == 0 {
+            return canvas;
+        }
+
+        let columns = self.weights(width, large.width() as usize);
+        let rows = self.weights(height, large.height() as usize);
+
+        // One sample row filtered down to the target width
+        let narrow = |y: usize| -> Vec<[f64; 4]> {
+            let row = large.row(y as i32);
+            columns
+                .iter()
+                .map(|taps| {
+                    let mut sum = [0.0; 4];
+                    for &(x, weight) in taps {
+                        for c in 0..4 {
+                            sum[c] += row[x][c] as f64 * weight;
+                        }
+                    }
+                    sum
+                })
+                .collect()
+        };
+
+        // Consecutive filtered rows, starting at sample row `first`
+        let mut band: VecDeque<Vec<[f64; 4]>> = VecDeque::new();
+        let mut first = 0;
+        for (y, taps) in rows.iter().enumerate() {
+            let (Some(&(top, _)), Some(&(bottom, _))) = (taps.first(), taps.last()) else {
+                continue;
+            };
+            while first < top && !band.is_empty() {
+                band.pop_front();
+                first += 1;
+            }
+            if band.is_empty() {
+                first = top;
+            }
+            while first + band.len() <= bottom {
+                band.push_back(narrow(first + band.len()));
+            }
+
+            for (x, pixel) in canvas.row_mut(y as i32).iter_mut().enumerate() {
+                let mut sum = [0.0; 4];
+                for &(sample, weight) in taps {
+                    for c in 0..4 {
+                        sum[c] += band[sample - first][x][c] * weight;
+                    }
+                }
+                *pixel = sum.map(|channel| channel.round().clamp(0.0, 255.0) as u8);
+            }
+        }
+        canvas
+    }
+}
+
+// Unit tests for supersampling
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometrical_shapes::golden::{self, Tolerance};
+    use crate::geometrical_shapes::{
+        ClipRect, Cubes, Displayable, Drawable, Pentagon, Point, Styled,
+    };
+    use raster::Color;
+
+    // Test box filtering averages each block of samples
+    #[test]
+    fn test_supersample_box() {
+        let mut large = Canvas::new(4, 2);
+        large.display(0, 0, Color::rgba(255, 255, 255, 255));
+        large.display(1, 1, Color::rgba(100, 0, 0, 255));
+        large.fill_rect(&ClipRect::new(2, 0, 3, 1), &Color::rgba(0, 0, 255, 0));
+
+        let small = Supersample::new(2).downsample(&large);
+        assert_eq!((small.width(), small.height()), (2, 1));
+        assert_eq!(small.row(0), &[[89, 64, 64, 255], [0, 0, 255, 0]]);
+
+        // Samples past a whole multiple of the factor are dropped
+        assert_eq!(
+            Supersample::new(2).downsample(&Canvas::new(5, 3)).width(),
+            2
+        );
+        assert_eq!(Supersample::new(0).factor(), 1);
+    }
+
+    // Test the Lanczos filter keeps flat areas flat and softens edges
+    #[test]
+    fn test_supersample_lanczos() {
+        let sampler = Supersample::new(4).with_filter(Downsample::Lanczos);
+        let flat = sampler.render(6, 6, |canvas, _| canvas.clear(&Color::rgb(40, 80, 120)));
+        assert!(flat.row(3).iter().all(|pixel| *pixel == [40, 80, 120, 255]));
+
+        // White on the right half, with the edge between two target pixels
+        // Ringing only shifts the flat ends by a few levels
+        let edge = sampler.render(6, 1, |canvas, scale| {
+            canvas.fill_rect(
+                &ClipRect::new(3 * scale, 0, 6 * scale, scale),
+                &Color::white(),
+            )
+        });
+        let reds: Vec<u8> = edge.row(0).iter().map(|pixel| pixel[0]).collect();
+        assert!(reds[0] < 8 && reds[5] > 247, "{reds:?}");
+        assert!(reds[2] < 64 && reds[3] > 192, "{reds:?}");
+        assert!(lanczos(1.0).abs() < 1e-12 && lanczos(3.5) == 0.0);
+    }
+
+    // Test every shape is smoothed without any code of its own
+    #[test]
+    fn test_supersample_shapes() {
+        let image = Supersample::new(4)
+            .render(80, 60, |canvas, scale| {
+                let at = |x, y| Point::new(x * scale, y * scale);
+                Cubes::from_center(&at(24, 30), 20 * scale)
+                    .with_rotation(0.3)
+                    .with_width(scale)
+                    .draw(canvas);
+                Pentagon::from_center(&at(60, 30), 15 * scale)
+                    .with_width(scale)
+                    .draw(canvas);
+            })
+            .to_image();
+
+        let partial = image
+            .bytes
+            .chunks_exact(4)
+            .filter(|pixel| pixel[..3].iter().any(|&c| c > 0 && c < 255))
+            .count();
+        assert!(partial > 100, "{partial}");
+        golden::assert_golden("supersample", &image, Tolerance::EXACT);
+    }
+}
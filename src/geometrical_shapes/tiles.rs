@@ -0,0 +1,213 @@
+use super::canvas::rgba;
+use super::{Canvas, ClipRect, Displayable, Drawable};
+use raster::Color;
+use rayon::prelude::*;
+
+/// Height in pixels of the bands of rows `main.rs` renders with
+#[allow(dead_code)]
+pub const TILE_SIZE: i32 = 64;
+
+/// A band of canvas rows drawn on in canvas coordinates
+/// Pixels outside its area are dropped, so neighbouring bands never race
+struct Band<'a> {
+    /// The rows of the band, full canvas width
+    pixels: &'a mut [[u8; 4]],
+    width: i32,
+    area: ClipRect,
+}
+
+impl Displayable for Band<'_> {
+    fn display(&mut self, x: i32, y: i32, color: Color) {
+        if self.area.contains(x, y) {
+            self.pixels[((y - self.area.top) * self.width + x) as usize] = rgba(&color);
+        }
+    }
+
+    fn display_span(&mut self, left: i32, right: i32, y: i32, color: Color) {
+        if y < self.area.top || y > self.area.bottom {
+            return;
+        }
+        let (left, right) = (left.max(self.area.left), right.min(self.area.right));
+        if left <= right {
+            let row = ((y - self.area.top) * self.width) as usize;
+            self.pixels[row + left as usize..=row + right as usize].fill(rgba(&color));
+        }
+    }
+
+    fn bounds(&self) -> Option<ClipRect> {
+        Some(self.area)
+    }
+}
+
+/// Draws `shapes` in order onto `canvas`, in bands of `tile_size` rows rendered in parallel
+/// Each shape is drawn once into every band its bounding box touches, clipped to the band
+/// Every pixel belongs to one band and sees the shapes in scene order,
+/// so the result is identical to drawing them one after another
+#[allow(dead_code)]
+pub fn render_tiled(canvas: &mut Canvas, shapes: &[&(dyn Drawable + Sync)], tile_size: i32) {
+    let (width, height) = (canvas.width(), canvas.height());
+    if width == 0 || height == 0 {
+        return;
+    }
+    let tile_size = tile_size.clamp(1, height);
+    let rows = (height + tile_size - 1) / tile_size;
+
+    // Indices of the shapes reaching each band, in scene order
+    let surface = ClipRect::from_size(width, height);
+    let mut bins: Vec<Vec<usize>> = vec![Vec::new(); rows as usize];
+    for (index, shape) in shapes.iter().enumerate() {
+        let area = shape
+            .bounding_box()
+            .map_or(surface, |area| area.intersect(&surface));
+        if area.is_empty() {
+            continue;
+        }
+        for row in area.top / tile_size..=area.bottom / tile_size {
+            bins[row as usize].push(index);
+        }
+    }
+
+    canvas
+        .pixels_mut()
+        .par_chunks_mut(tile_size as usize * width as usize)
+        .zip(bins.par_iter())
+        .enumerate()
+        .for_each(|(row, (pixels, bin))| {
+            let top = row as i32 * tile_size;
+            let mut band = Band {
+                pixels,
+                width,
+                area: ClipRect::new(0, top, width - 1, top + tile_size - 1).intersect(&surface),
+            };
+            for &index in bin {
+                shapes[index].draw(&mut band);
+            }
+        });
+}
+
+// Unit tests for tiled rendering
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometrical_shapes::{
+        Circle, Cubes, Line, Pentagon, Point, Polygon, Rectangle, Styled, Triangle,
+    };
+    use std::time::Instant;
+
+    /// A crowded scene of every kind of shape, some off the canvas
+    fn scene(width: i32, height: i32) -> Vec<Box<dyn Drawable + Sync>> {
+        let mut shapes: Vec<Box<dyn Drawable + Sync>> = Vec::new();
+        for _ in 0..40 {
+            shapes.push(Box::new(Circle::random(width, height)));
+            shapes.push(Box::new(Line::random(width, height)));
+        }
+        shapes.push(Box::new(
+            Rectangle::new(&Point::new(-20, 10), &Point::new(90, 70)).with_fill(Color::green()),
+        ));
+        shapes.push(Box::new(
+            Triangle::new(
+                &Point::new(10, 140),
+                &Point::new(100, 5),
+                &Point::new(190, 120),
+            )
+            .with_fill(Color::blue()),
+        ));
+        shapes.push(Box::new(
+            Polygon::new(&[Point::new(30, 30), Point::new(170, 60), Point::new(60, 130)])
+                .with_fill(Color::rgba(200, 100, 0, 128)),
+        ));
+        shapes.push(Box::new(Cubes::new(&Point::new(120, 80), 50)));
+        shapes.push(Box::new(Pentagon::new(&Point::new(60, 100), 40)));
+        shapes.push(Box::new(Circle::new(&Point::new(900, 900), 30)));
+        shapes
+    }
+
+    // Test bands of any height give the same bytes as drawing one shape after another
+    #[test]
+    fn test_tiles_match_sequential() {
+        let shapes = scene(200, 150);
+        let shapes: Vec<&(dyn Drawable + Sync)> =
+            shapes.iter().map(|shape| shape.as_ref()).collect();
+
+        let mut sequential = Canvas::new(200, 150);
+        for shape in &shapes {
+            shape.draw(&mut sequential);
+        }
+
+        for tile_size in [1, 5, 32, 64, 150, 1000] {
+            let mut tiled = Canvas::new(200, 150);
+            render_tiled(&mut tiled, &shapes, tile_size);
+            assert!(tiled == sequential, "tile size {tile_size}");
+        }
+    }
+
+    // Benchmark tiled against sequential rendering of a poster-sized scene
+    // Run with `make bench`
+    #[test]
+    #[ignore]
+    fn bench_poster_scene() {
+        let (width, height) = (4000, 3000);
+        let mut scene: Vec<Box<dyn Drawable + Sync>> = Vec::new();
+        for _ in 0..5000 {
+            scene.push(Box::new(
+                Circle::random(width, height).with_fill(Color::red()),
+            ));
+            scene.push(Box::new(Line::random(width, height)));
+        }
+        let shapes: Vec<&(dyn Drawable + Sync)> =
+            scene.iter().map(|shape| shape.as_ref()).collect();
+
+        let mut sequential = Canvas::new(width, height);
+        let start = Instant::now();
+        for shape in &shapes {
+            shape.draw(&mut sequential);
+        }
+        let slow = start.elapsed();
+
+        let mut tiled = Canvas::new(width, height);
+        let start = Instant::now();
+        render_tiled(&mut tiled, &shapes, TILE_SIZE);
+        let fast = start.elapsed();
+
+        println!("sequential: {slow:?}");
+        println!("tiled:      {fast:?}");
+        assert!(tiled == sequential);
+    }
+
+    // Test bands only draw pixels of their own area and keep the background
+    #[test]
+    fn test_tiles_clip() {
+        let mut canvas = Canvas::filled(10, 10, &Color::white());
+        let rectangle = ClipRect::new(4, 0, 7, 3);
+        let mut band = Band {
+            pixels: &mut canvas.pixels_mut()[..40],
+            width: 10,
+            area: rectangle,
+        };
+        band.display(3, 1, Color::red());
+        band.display(5, 1, Color::red());
+        band.display_span(-10, 20, 2, Color::blue());
+        band.display_span(0, 9, 4, Color::blue());
+
+        assert_eq!(canvas.get_pixel(3, 1).unwrap().g, 255);
+        assert_eq!(canvas.get_pixel(5, 1).unwrap().g, 0);
+        let blue: Vec<i32> = (0..10)
+            .filter(|&x| canvas.get_pixel(x, 2).unwrap().r == 0)
+            .collect();
+        assert_eq!(blue, [4, 5, 6, 7]);
+        assert!((0..10).all(|x| canvas.get_pixel(x, 4).unwrap().r == 255));
+    }
+
+    // Test empty canvases and scenes leave nothing behind
+    #[test]
+    fn test_tiles_empty() {
+        let circle = Circle::new(&Point::new(5, 5), 3);
+        let mut empty = Canvas::new(0, 0);
+        render_tiled(&mut empty, &[&circle], 4);
+        assert!(empty.as_bytes().is_empty());
+
+        let mut canvas = Canvas::new(9, 9);
+        render_tiled(&mut canvas, &[], 0);
+        assert!(canvas == Canvas::new(9, 9));
+    }
+}
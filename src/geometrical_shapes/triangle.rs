@@ -1,31 +1,28 @@
-use super::{Drawable, Line, Point};
+use super::stroke::StrokeMask;
+use super::{
+    Animatable, ClipRect, Displayable, Drawable, Point, Polygon, Polygonal, Style, Styled,
+};
 use rand::Rng;
-use raster::{Color, Image};
+use raster::Color;
 
-/// Struct to represent a triangle with vertices and color
+/// Struct to represent a triangle with vertices and a style
+#[derive(Clone)]
 pub struct Triangle {
-    tris: Vec<(Point, Point, Point, Color)>,
+    tris: Vec<(Point, Point, Point)>,
+    style: Style,
 }
 
 #[allow(dead_code)]
 impl Triangle {
-    /// Creates a triangle from 3 given points with a random color
+    /// Creates a triangle from 3 given points
+    /// Kept for compatibility, same as `from_vertices`
     pub fn new(a: &Point, b: &Point, c: &Point) -> Self {
-        let mut rng = rand::thread_rng();
-        let mut tris = Vec::new();
-
-        let color = Color::rgb(
-            rng.gen_range(150..255),
-            rng.gen_range(150..255),
-            rng.gen_range(150..255),
-        );
-
-        tris.push((a.clone(), b.clone(), c.clone(), color));
-
-        Triangle { tris }
+        Triangle::from_vertices(a, b, c)
     }
 
     /// Generates a random triangle based on random dimensions and color
+    /// Ignores its arguments and places it in an 800x800 area,
+    /// kept for compatibility, see `random_in`
     pub fn random(_a: &Point, _b: &Point, _c: &Point) -> Self {
         let mut rng = rand::thread_rng();
         let mut tris = Vec::new();
@@ -33,43 +30,112 @@ impl Triangle {
         let base = Point::random(800, 800);
         let height = rng.gen_range(80..180);
         let width = rng.gen_range(60..150);
-        let color = Color::rgb(
-            rng.gen_range(150..255),
-            rng.gen_range(150..255),
-            rng.gen_range(150..255),
-        );
 
         tris.push((
             base.clone(),
             Point::new(base.x + width, base.y),
             Point::new(base.x + width / 2, base.y - height),
-            color,
         ));
 
-        Triangle { tris }
+        Triangle {
+            tris,
+            style: Style::random(150..255),
+        }
+    }
+
+    /// Creates a triangle from 3 given points, stroked 1px wide in white
+    pub fn from_vertices(a: &Point, b: &Point, c: &Point) -> Self {
+        Triangle {
+            tris: vec![(a.clone(), b.clone(), c.clone())],
+            style: Style::new(),
+        }
+    }
+
+    /// Generates a triangle of random position and size lying entirely
+    /// inside a `width` by `height` area, stroked 1px wide in white
+    /// Sizes are picked as in `random`, shrunk to fit small areas
+    pub fn random_in(width: i32, height: i32) -> Self {
+        let mut rng = rand::thread_rng();
+        let (max_x, max_y) = (
+            width.saturating_sub(1).max(0),
+            height.saturating_sub(1).max(0),
+        );
+
+        let tri_width = rng.gen_range(60..150).min(max_x);
+        let tri_height = rng.gen_range(80..180).min(max_y);
+        let base = Point::new(
+            rng.gen_range(0..=max_x - tri_width),
+            rng.gen_range(tri_height..=max_y),
+        );
+
+        Triangle::from_vertices(
+            &base,
+            &Point::new(base.x + tri_width, base.y),
+            &Point::new(base.x + tri_width / 2, base.y - tri_height),
+        )
+    }
+}
+
+impl Styled for Triangle {
+    fn style(&self) -> &Style {
+        &self.style
+    }
+
+    fn style_mut(&mut self) -> &mut Style {
+        &mut self.style
+    }
+}
+
+impl Animatable for Triangle {
+    /// Moves the vertices of every triangle and blends the style
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        Triangle {
+            tris: self.tris.interpolate(&to.tris, t),
+            style: self.style.interpolate(&to.style, t),
+        }
     }
 }
 
 /// Implementation of the Drawable trait for Triangle
 impl Drawable for Triangle {
-    /// Draws the triangle onto the given image
-    fn draw(&self, image: &mut Image) {
-        let thickness = 1;
-
-        for (a, b, c, color) in &self.tris {
-            let edge_ab = Line::from_points(a, b, thickness, color.clone());
-            let edge_bc = Line::from_points(b, c, thickness, color.clone());
-            let edge_ca = Line::from_points(c, a, thickness, color.clone());
-
-            edge_ab.draw(image);
-            edge_bc.draw(image);
-            edge_ca.draw(image);
+    /// Draws the triangle onto the given image, each outline pixel once
+    fn draw(&self, image: &mut dyn Displayable) {
+        if !self.is_visible_on(image) {
+            return;
+        }
+
+        for (a, b, c) in &self.tris {
+            let vertices = [a.clone(), b.clone(), c.clone()];
+            self.style.paint_fill(&[&vertices], image);
+
+            let mut mask = StrokeMask::new(image).styled(&self.style);
+            mask.add_ring(&vertices, self.style.width);
+            self.style.paint_stroke(mask, image);
         }
     }
 
-    // Returns a default a fallback color
+    /// Returns the color of the triangle's style
     fn color(&self) -> Color {
-        Color::rgb(0, 0, 0)
+        self.style.color()
+    }
+
+    /// Covers every triangle's vertices, widened by the edges
+    fn bounding_box(&self) -> Option<ClipRect> {
+        ClipRect::around(self.tris.iter().flat_map(|(a, b, c)| [a, b, c]))
+            .map(|area| area.expand(self.style.reach()))
+    }
+}
+
+impl Polygonal for Triangle {
+    /// Flattens each triangle into its three vertices
+    fn to_polygons(&self) -> Vec<Polygon> {
+        self.tris
+            .iter()
+            .map(|(a, b, c)| {
+                Polygon::from_vertices(&[a.clone(), b.clone(), c.clone()], self.color())
+                    .with_style(self.style.clone())
+            })
+            .collect()
     }
 }
 
@@ -77,6 +143,8 @@ impl Drawable for Triangle {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::geometrical_shapes::golden::{self, Tolerance};
+    use raster::Image;
 
     // Test creation of a triangle using given points
     #[test]
@@ -87,7 +155,7 @@ mod tests {
         let triangle = Triangle::new(&a, &b, &c);
 
         assert_eq!(triangle.tris.len(), 1);
-        let (tri_a, tri_b, tri_c, _color) = &triangle.tris[0];
+        let (tri_a, tri_b, tri_c) = &triangle.tris[0];
         assert_eq!(tri_a.x, a.x);
         assert_eq!(tri_a.y, a.y);
         assert_eq!(tri_b.x, b.x);
@@ -105,7 +173,7 @@ mod tests {
         let triangle = Triangle::random(&a, &b, &c);
 
         // Check each triangle's properties
-        for (base, right, top, _color) in &triangle.tris {
+        for (base, right, top) in &triangle.tris {
             assert!(base.x >= 0 && base.x <= 800);
             assert!(base.y >= 0 && base.y <= 800);
             assert!(right.x > base.x);
@@ -127,7 +195,7 @@ mod tests {
         triangle.draw(&mut image);
     }
 
-    // Test color function returns black
+    // Test color function returns the stroke color of the style
     #[test]
     fn test_triangle_color() {
         let a = Point::new(100, 100);
@@ -136,9 +204,11 @@ mod tests {
         let triangle = Triangle::new(&a, &b, &c);
 
         let color = triangle.color();
-        assert_eq!(color.r, 0);
-        assert_eq!(color.g, 0);
-        assert_eq!(color.b, 0);
+        assert_eq!((color.r, color.g, color.b), (255, 255, 255));
+
+        let styled = Triangle::new(&a, &b, &c).with_style(Style::new().stroke(Color::blue()));
+        assert_eq!(styled.color().b, 255);
+        assert_eq!(styled.color().r, 0);
     }
 
     #[test]
@@ -164,4 +234,48 @@ mod tests {
         let triangle = Triangle::new(&a, &b, &c);
         assert_eq!(triangle.tris.len(), 1);
     }
+
+    // Test triangle outlines against their golden image
+    #[test]
+    fn test_triangle_golden() {
+        let triangle = Triangle::new(&Point::new(50, 5), &Point::new(95, 90), &Point::new(5, 70))
+            .with_stroke(Color::green());
+
+        let image = golden::render(100, 100, &[&triangle]);
+        golden::assert_golden("triangle", &image, Tolerance::EXACT);
+    }
+
+    // Test explicit vertices keep their order and a fixed white stroke
+    #[test]
+    fn test_triangle_from_vertices() {
+        let (a, b, c) = (Point::new(0, 0), Point::new(10, 0), Point::new(5, 8));
+        let triangle = Triangle::from_vertices(&a, &b, &c);
+
+        let (tri_a, tri_b, tri_c) = &triangle.tris[0];
+        assert_eq!((tri_a, tri_b, tri_c), (&a, &b, &c));
+        let color = triangle.color();
+        assert_eq!((color.r, color.g, color.b), (255, 255, 255));
+    }
+
+    // Test random triangles stay inside the requested area, however small
+    #[test]
+    fn test_triangle_random_in() {
+        for (width, height) in [
+            (300, 200),
+            (1000, 1000),
+            (50, 20),
+            (1, 1),
+            (0, -5),
+            (i32::MIN, i32::MIN),
+        ] {
+            for _ in 0..20 {
+                let triangle = Triangle::random_in(width, height);
+                let (a, b, c) = &triangle.tris[0];
+                for vertex in [a, b, c] {
+                    assert!(vertex.x >= 0 && vertex.x <= width.saturating_sub(1).max(0));
+                    assert!(vertex.y >= 0 && vertex.y <= height.saturating_sub(1).max(0));
+                }
+            }
+        }
+    }
 }
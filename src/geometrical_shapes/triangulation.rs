@@ -0,0 +1,260 @@
+use super::{Point, Triangle};
+
+/// Cross product of vectors `o -> a` and `o -> b`
+/// Positive when `a -> b` turns counter-clockwise around `o`
+fn cross(o: &Point, a: &Point, b: &Point) -> i128 {
+    (a.x as i128 - o.x as i128) * (b.y as i128 - o.y as i128)
+        - (a.y as i128 - o.y as i128) * (b.x as i128 - o.x as i128)
+}
+
+/// Twice the signed area of a ring, positive for counter-clockwise order
+fn signed_area(ring: &[Point]) -> i128 {
+    (0..ring.len())
+        .map(|i| {
+            let a = &ring[i];
+            let b = &ring[(i + 1) % ring.len()];
+            a.x as i128 * b.y as i128 - b.x as i128 * a.y as i128
+        })
+        .sum()
+}
+
+/// Returns a copy of the ring in the requested winding order
+fn oriented(ring: &[Point], counter_clockwise: bool) -> Vec<Point> {
+    let mut ring = ring.to_vec();
+    ring.dedup();
+    if ring.len() > 1 && ring[0] == ring[ring.len() - 1] {
+        ring.pop();
+    }
+    if (signed_area(&ring) > 0) != counter_clockwise {
+        ring.reverse();
+    }
+    ring
+}
+
+/// Checks whether segments `a-b` and `c-d` cross at a point interior to both
+fn segments_cross(a: &Point, b: &Point, c: &Point, d: &Point) -> bool {
+    let d1 = cross(a, b, c);
+    let d2 = cross(a, b, d);
+    let d3 = cross(c, d, a);
+    let d4 = cross(c, d, b);
+    ((d1 > 0 && d2 < 0) || (d1 < 0 && d2 > 0)) && ((d3 > 0 && d4 < 0) || (d3 < 0 && d4 > 0))
+}
+
+/// Checks whether `p` lies inside or on the counter-clockwise triangle `a, b, c`
+fn in_triangle(p: &Point, a: &Point, b: &Point, c: &Point) -> bool {
+    cross(a, b, p) >= 0 && cross(b, c, p) >= 0 && cross(c, a, p) >= 0
+}
+
+/// Splices a clockwise hole into a counter-clockwise outline
+/// Connects the hole's rightmost vertex to the closest outline vertex
+/// whose bridge does not cross any edge, duplicating both bridge ends
+fn merge_hole(outline: &[Point], hole: &[Point], others: &[Vec<Point>]) -> Vec<Point> {
+    let m = (0..hole.len())
+        .max_by_key(|&i| (hole[i].x, std::cmp::Reverse(hole[i].y)))
+        .unwrap_or(0);
+    let anchor = &hole[m];
+
+    let edges = |ring: &[Point]| -> Vec<(Point, Point)> {
+        (0..ring.len())
+            .map(|i| (ring[i].clone(), ring[(i + 1) % ring.len()].clone()))
+            .collect()
+    };
+    let mut blockers = edges(outline);
+    blockers.extend(edges(hole));
+    for other in others {
+        blockers.extend(edges(other));
+    }
+
+    let distance = |p: &Point| {
+        let dx = p.x as i64 - anchor.x as i64;
+        let dy = p.y as i64 - anchor.y as i64;
+        dx * dx + dy * dy
+    };
+    let mut candidates: Vec<usize> = (0..outline.len()).collect();
+    candidates.sort_by_key(|&i| (outline[i].x < anchor.x, distance(&outline[i])));
+
+    let bridge = candidates
+        .iter()
+        .copied()
+        .find(|&i| {
+            let p = &outline[i];
+            blockers
+                .iter()
+                .all(|(a, b)| !segments_cross(anchor, p, a, b))
+        })
+        .unwrap_or(candidates[0]);
+
+    let mut merged = Vec::with_capacity(outline.len() + hole.len() + 2);
+    merged.extend_from_slice(&outline[..=bridge]);
+    merged.extend(hole[m..].iter().cloned());
+    merged.extend(hole[..=m].iter().cloned());
+    merged.extend_from_slice(&outline[bridge..]);
+    merged
+}
+
+/// Triangulates a simple polygon with optional holes using ear clipping
+/// Returns counter-clockwise vertex triples covering the polygon's interior
+pub(super) fn ear_clip(outline: &[Point], holes: &[Vec<Point>]) -> Vec<[Point; 3]> {
+    let mut ring = oriented(outline, true);
+    if ring.len() < 3 {
+        return Vec::new();
+    }
+
+    let mut holes: Vec<Vec<Point>> = holes
+        .iter()
+        .map(|hole| oriented(hole, false))
+        .filter(|hole| hole.len() >= 3)
+        .collect();
+    // Rightmost holes first so later bridges can route around earlier ones
+    holes.sort_by_key(|hole| std::cmp::Reverse(hole.iter().map(|p| p.x).max()));
+    for i in 0..holes.len() {
+        ring = merge_hole(&ring, &holes[i], &holes[i + 1..]);
+    }
+
+    let mut remaining: Vec<usize> = (0..ring.len()).collect();
+    let mut triangles = Vec::with_capacity(ring.len().saturating_sub(2));
+
+    while remaining.len() > 3 {
+        let n = remaining.len();
+        let corner = |k: usize| {
+            (
+                &ring[remaining[(k + n - 1) % n]],
+                &ring[remaining[k]],
+                &ring[remaining[(k + 1) % n]],
+            )
+        };
+
+        let is_ear = |k: usize| {
+            let (a, b, c) = corner(k);
+            cross(a, b, c) > 0
+                && remaining.iter().all(|&v| {
+                    let p = &ring[v];
+                    p == a || p == b || p == c || !in_triangle(p, a, b, c)
+                })
+        };
+
+        // Prefer a proper ear, fall back to dropping flat or convex corners
+        // so that degenerate input still terminates
+        let Some(k) = (0..n)
+            .find(|&k| is_ear(k))
+            .or_else(|| (0..n).find(|&k| cross(corner(k).0, corner(k).1, corner(k).2) == 0))
+            .or_else(|| (0..n).find(|&k| cross(corner(k).0, corner(k).1, corner(k).2) > 0))
+        else {
+            break;
+        };
+
+        let (a, b, c) = corner(k);
+        if cross(a, b, c) > 0 {
+            triangles.push([a.clone(), b.clone(), c.clone()]);
+        }
+        remaining.remove(k);
+    }
+
+    if remaining.len() == 3 {
+        let (a, b, c) = (
+            &ring[remaining[0]],
+            &ring[remaining[1]],
+            &ring[remaining[2]],
+        );
+        if cross(a, b, c) > 0 {
+            triangles.push([a.clone(), b.clone(), c.clone()]);
+        }
+    }
+
+    triangles
+}
+
+/// Triangulates a polygon outline with optional holes into drawable triangles
+/// Uses ear clipping, holes are bridged into the outline first
+#[allow(dead_code)]
+pub fn triangulate(outline: &[Point], holes: &[Vec<Point>]) -> Vec<Triangle> {
+    ear_clip(outline, holes)
+        .iter()
+        .map(|[a, b, c]| Triangle::new(a, b, c))
+        .collect()
+}
+
+// Unit tests for ear clipping
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(coords: &[(i32, i32)]) -> Vec<Point> {
+        coords.iter().map(|&(x, y)| Point::new(x, y)).collect()
+    }
+
+    fn total_area(triangles: &[[Point; 3]]) -> i128 {
+        triangles.iter().map(|[a, b, c]| cross(a, b, c)).sum()
+    }
+
+    // Test a convex quad splits into two triangles with the same area
+    #[test]
+    fn test_ear_clip_square() {
+        let square = points(&[(0, 0), (10, 0), (10, 10), (0, 10)]);
+        let triangles = ear_clip(&square, &[]);
+
+        assert_eq!(triangles.len(), 2);
+        assert_eq!(total_area(&triangles), signed_area(&square));
+    }
+
+    // Test a concave outline given clockwise is still fully covered
+    #[test]
+    fn test_ear_clip_concave() {
+        let arrow = points(&[(0, 0), (5, 4), (10, 0), (10, 10), (0, 10)]);
+        let clockwise: Vec<Point> = arrow.iter().rev().cloned().collect();
+        let triangles = ear_clip(&clockwise, &[]);
+
+        assert_eq!(triangles.len(), 3);
+        assert_eq!(total_area(&triangles), signed_area(&arrow).abs());
+        for [a, b, c] in &triangles {
+            assert!(cross(a, b, c) > 0);
+        }
+    }
+
+    // Test holes are subtracted from the covered area
+    #[test]
+    fn test_ear_clip_with_holes() {
+        let outline = points(&[(0, 0), (30, 0), (30, 20), (0, 20)]);
+        let holes = vec![
+            points(&[(5, 5), (10, 5), (10, 15), (5, 15)]),
+            points(&[(20, 5), (25, 5), (25, 15), (20, 15)]),
+        ];
+        let triangles = ear_clip(&outline, &holes);
+
+        let expected = signed_area(&outline) - 2 * 50 * 2;
+        assert_eq!(total_area(&triangles), expected);
+
+        // No triangle may cover the center of a hole
+        for hole_center in points(&[(7, 10), (22, 10)]) {
+            for [a, b, c] in &triangles {
+                assert!(!in_triangle(&hole_center, a, b, c));
+            }
+        }
+    }
+
+    // Test triangulate wraps every ear into a Triangle
+    #[test]
+    fn test_triangulate() {
+        let pentagon = points(&[(10, 0), (20, 8), (16, 20), (4, 20), (0, 8)]);
+        assert_eq!(triangulate(&pentagon, &[]).len(), 3);
+    }
+
+    // Test degenerate input terminates without panicking
+    #[test]
+    fn test_ear_clip_edge_cases() {
+        assert!(ear_clip(&[], &[]).is_empty());
+        assert!(ear_clip(&points(&[(0, 0), (5, 5)]), &[]).is_empty());
+        assert!(ear_clip(&points(&[(0, 0), (5, 5), (10, 10)]), &[]).is_empty());
+
+        let with_duplicates =
+            points(&[(0, 0), (0, 0), (10, 0), (10, 10), (10, 10), (0, 10), (0, 0)]);
+        assert_eq!(ear_clip(&with_duplicates, &[]).len(), 2);
+
+        let extreme = points(&[
+            (i32::MIN, i32::MIN),
+            (i32::MAX, i32::MIN),
+            (i32::MAX, i32::MAX),
+        ]);
+        assert_eq!(ear_clip(&extreme, &[]).len(), 1);
+    }
+}
@@ -0,0 +1,387 @@
+use super::Interpolation;
+use raster::Color;
+use std::f64::consts::PI;
+
+/// Values that can be blended between two states
+/// Implemented for numbers, colors, points, styles and every shape
+pub trait Animatable: Clone {
+    /// Blends `self` towards `to` by `t`, 0.0 giving `self` and 1.0 giving `to`
+    /// `t` may overshoot either end when an easing curve does
+    fn interpolate(&self, to: &Self, t: f64) -> Self;
+}
+
+/// Picks `from` for the first half of the blend and `to` for the second,
+/// for values that cannot be blended smoothly
+pub(super) fn snap<T: Clone>(from: &T, to: &T, t: f64) -> T {
+    if t < 0.5 { from.clone() } else { to.clone() }
+}
+
+impl Animatable for f64 {
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        self + (to - self) * t
+    }
+}
+
+impl Animatable for i32 {
+    /// Rounds to the nearest whole value, saturating at the ends of the range
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        (*self as f64).interpolate(&(*to as f64), t).round() as i32
+    }
+}
+
+impl Animatable for Color {
+    /// Blends through Oklab so colors stay bright halfway
+    /// Overshooting easings are held within the two colors
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        Interpolation::Oklab.mix(self, to, t.clamp(0.0, 1.0))
+    }
+}
+
+impl<A: Animatable, B: Animatable> Animatable for (A, B) {
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        (self.0.interpolate(&to.0, t), self.1.interpolate(&to.1, t))
+    }
+}
+
+impl<A: Animatable, B: Animatable, C: Animatable> Animatable for (A, B, C) {
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        (
+            self.0.interpolate(&to.0, t),
+            self.1.interpolate(&to.1, t),
+            self.2.interpolate(&to.2, t),
+        )
+    }
+}
+
+impl<T: Animatable> Animatable for Vec<T> {
+    /// Blends element by element
+    /// Lists of different lengths cannot be paired up and snap halfway instead
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        if self.len() != to.len() {
+            return snap(self, to, t);
+        }
+        self.iter()
+            .zip(to)
+            .map(|(from, to)| from.interpolate(to, t))
+            .collect()
+    }
+}
+
+/// Curves shaping how a blend speeds up and slows down over time
+/// Every curve maps 0.0 to 0.0 and 1.0 to 1.0, elastic curves overshoot in between
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Easing {
+    /// Constant speed
+    #[default]
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    /// Winds up with growing oscillations before springing to the end
+    ElasticIn,
+    /// Overshoots the end and settles with shrinking oscillations
+    ElasticOut,
+    ElasticInOut,
+    BounceIn,
+    /// Drops onto the end and bounces a few times like a ball
+    BounceOut,
+    BounceInOut,
+    /// CSS `cubic-bezier(x1, y1, x2, y2)`, the curve from (0, 0) to (1, 1)
+    /// through control points (x1, y1) and (x2, y2)
+    /// The x coordinates are clamped to 0.0..=1.0 so time never runs backwards
+    CubicBezier(f64, f64, f64, f64),
+}
+
+/// Ball bouncing onto 1.0 in four shrinking hops
+fn bounce_out(t: f64) -> f64 {
+    const N: f64 = 7.5625;
+    const D: f64 = 2.75;
+    if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+/// Value of the cubic bezier through 0, `p1`, `p2` and 1 at parameter `s`
+fn bezier(p1: f64, p2: f64, s: f64) -> f64 {
+    let u = 1.0 - s;
+    3.0 * u * u * s * p1 + 3.0 * u * s * s * p2 + s * s * s
+}
+
+/// Solves the bezier for the `y` reached when `x` equals `t`
+/// Newton's method converges in a few steps, bisection catches flat spots
+fn cubic_bezier(x1: f64, y1: f64, x2: f64, y2: f64, t: f64) -> f64 {
+    let (x1, x2) = (x1.clamp(0.0, 1.0), x2.clamp(0.0, 1.0));
+    let slope = |s: f64| {
+        let u = 1.0 - s;
+        3.0 * u * u * x1 + 6.0 * u * s * (x2 - x1) + 3.0 * s * s * (1.0 - x2)
+    };
+
+    let mut s = t;
+    for _ in 0..8 {
+        let error = bezier(x1, x2, s) - t;
+        if error.abs() < 1e-7 {
+            return bezier(y1, y2, s);
+        }
+        let d = slope(s);
+        if d.abs() < 1e-6 {
+            break;
+        }
+        s -= error / d;
+    }
+
+    let (mut low, mut high) = (0.0, 1.0);
+    s = t;
+    for _ in 0..50 {
+        let x = bezier(x1, x2, s);
+        if (x - t).abs() < 1e-7 {
+            break;
+        }
+        if x < t {
+            low = s;
+        } else {
+            high = s;
+        }
+        s = (low + high) / 2.0;
+    }
+    bezier(y1, y2, s)
+}
+
+#[allow(dead_code)]
+impl Easing {
+    /// CSS `ease`
+    pub const EASE: Easing = Easing::CubicBezier(0.25, 0.1, 0.25, 1.0);
+    /// CSS `ease-in`
+    pub const EASE_IN: Easing = Easing::CubicBezier(0.42, 0.0, 1.0, 1.0);
+    /// CSS `ease-out`
+    pub const EASE_OUT: Easing = Easing::CubicBezier(0.0, 0.0, 0.58, 1.0);
+    /// CSS `ease-in-out`
+    pub const EASE_IN_OUT: Easing = Easing::CubicBezier(0.42, 0.0, 0.58, 1.0);
+
+    /// Eased progress for `t`, clamped to 0.0..=1.0
+    pub fn apply(self, t: f64) -> f64 {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        // Out curves mirror in curves, in-out curves join both halves
+        let out = |ease: fn(f64) -> f64| 1.0 - ease(1.0 - t);
+        let in_out = |ease: fn(f64) -> f64| {
+            if t < 0.5 {
+                ease(2.0 * t) / 2.0
+            } else {
+                1.0 - ease(2.0 - 2.0 * t) / 2.0
+            }
+        };
+
+        let quad = |t: f64| t * t;
+        let cubic = |t: f64| t * t * t;
+        let elastic = |t: f64| {
+            if t <= 0.0 || t >= 1.0 {
+                t
+            } else {
+                -(2.0f64.powf(10.0 * t - 10.0)) * ((10.0 * t - 10.75) * 2.0 * PI / 3.0).sin()
+            }
+        };
+        let bounce = |t: f64| 1.0 - bounce_out(1.0 - t);
+
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => quad(t),
+            Easing::QuadOut => out(quad),
+            Easing::QuadInOut => in_out(quad),
+            Easing::CubicIn => cubic(t),
+            Easing::CubicOut => out(cubic),
+            Easing::CubicInOut => in_out(cubic),
+            Easing::ElasticIn => elastic(t),
+            Easing::ElasticOut => out(elastic),
+            Easing::ElasticInOut => in_out(elastic),
+            Easing::BounceIn => bounce(t),
+            Easing::BounceOut => bounce_out(t),
+            Easing::BounceInOut => in_out(bounce),
+            Easing::CubicBezier(x1, y1, x2, y2) => cubic_bezier(x1, y1, x2, y2, t),
+        }
+    }
+}
+
+/// A blend between two states of a value, shaped by an easing curve
+/// The states can be whole shapes, to morph one into the other
+#[derive(Debug, Clone)]
+pub struct Tween<T> {
+    from: T,
+    to: T,
+    easing: Easing,
+}
+
+#[allow(dead_code)]
+impl<T: Animatable> Tween<T> {
+    /// Creates a linear tween from `from` to `to`
+    pub fn new(from: T, to: T) -> Self {
+        Tween {
+            from,
+            to,
+            easing: Easing::Linear,
+        }
+    }
+
+    /// Sets the easing curve
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// State at progress `t` from 0.0 to 1.0, after easing
+    pub fn at(&self, t: f64) -> T {
+        self.from.interpolate(&self.to, self.easing.apply(t))
+    }
+
+    /// `count` states evenly spaced from `from` to `to`, both included,
+    /// for sets of in-between variants
+    pub fn steps(&self, count: usize) -> Vec<T> {
+        match count {
+            0 => Vec::new(),
+            1 => vec![self.from.clone()],
+            _ => (0..count)
+                .map(|i| self.at(i as f64 / (count - 1) as f64))
+                .collect(),
+        }
+    }
+}
+
+// Unit tests for easing and tweens
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometrical_shapes::golden::{self, Tolerance};
+    use crate::geometrical_shapes::{Circle, Drawable, Point, Rectangle, Style, Styled};
+
+    const CURVES: [Easing; 14] = [
+        Easing::Linear,
+        Easing::QuadIn,
+        Easing::QuadOut,
+        Easing::QuadInOut,
+        Easing::CubicIn,
+        Easing::CubicOut,
+        Easing::CubicInOut,
+        Easing::ElasticIn,
+        Easing::ElasticOut,
+        Easing::ElasticInOut,
+        Easing::BounceIn,
+        Easing::BounceOut,
+        Easing::BounceInOut,
+        Easing::EASE,
+    ];
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-4
+    }
+
+    // Test every curve starts at 0.0, ends at 1.0 and clamps its input
+    #[test]
+    fn test_easing_ends() {
+        for easing in CURVES {
+            assert!(close(easing.apply(0.0), 0.0), "{easing:?}");
+            assert!(close(easing.apply(1.0), 1.0), "{easing:?}");
+            assert_eq!(easing.apply(-3.0), easing.apply(0.0));
+            assert_eq!(easing.apply(4.0), easing.apply(1.0));
+            assert_eq!(easing.apply(f64::NAN), easing.apply(0.0));
+        }
+    }
+
+    // Test curves against known values
+    #[test]
+    fn test_easing_values() {
+        assert!(close(Easing::QuadIn.apply(0.5), 0.25));
+        assert!(close(Easing::QuadOut.apply(0.5), 0.75));
+        assert!(close(Easing::CubicIn.apply(0.5), 0.125));
+        assert!(close(Easing::CubicInOut.apply(0.25), 0.0625));
+        assert!(close(Easing::BounceOut.apply(1.0 / 2.75), 1.0));
+        for easing in [Easing::QuadInOut, Easing::CubicInOut, Easing::ElasticInOut] {
+            assert!(close(easing.apply(0.5), 0.5), "{easing:?}");
+        }
+
+        // Elastic curves overshoot, bounces never leave 0.0..=1.0
+        assert!((0..100).any(|i| Easing::ElasticOut.apply(i as f64 / 100.0) > 1.0));
+        assert!(
+            (0..=100).all(|i| (0.0..=1.0).contains(&Easing::BounceOut.apply(i as f64 / 100.0)))
+        );
+    }
+
+    // Test cubic beziers match the CSS keywords and handle straight and steep curves
+    #[test]
+    fn test_easing_cubic_bezier() {
+        // Values of CSS `ease` from browser implementations
+        assert!((Easing::EASE.apply(0.25) - 0.4085).abs() < 1e-3);
+        assert!((Easing::EASE.apply(0.5) - 0.8024).abs() < 1e-3);
+        assert!(close(Easing::EASE_IN_OUT.apply(0.5), 0.5));
+
+        let straight = Easing::CubicBezier(0.25, 0.25, 0.75, 0.75);
+        for i in 0..=10 {
+            let t = i as f64 / 10.0;
+            assert!(close(straight.apply(t), t));
+        }
+
+        // Flat tangents at both ends still solve
+        let steep = Easing::CubicBezier(1.0, 0.0, 0.0, 1.0);
+        assert!(close(steep.apply(0.5), 0.5));
+        let mut previous = 0.0;
+        for i in 0..=100 {
+            let y = steep.apply(i as f64 / 100.0);
+            assert!(y >= previous - 1e-6);
+            previous = y;
+        }
+    }
+
+    // Test tweens blend numbers, lists and tuples and ease their progress
+    #[test]
+    fn test_tween_values() {
+        let tween = Tween::new(0.0, 100.0).with_easing(Easing::QuadIn);
+        assert_eq!(tween.at(0.5), 25.0);
+        assert_eq!(tween.steps(5), vec![0.0, 6.25, 25.0, 56.25, 100.0]);
+        assert!(tween.steps(0).is_empty());
+        assert_eq!(tween.steps(1), vec![0.0]);
+
+        let pairs = Tween::new(vec![(0, 10.0)], vec![(10, 20.0)]);
+        assert_eq!(pairs.at(0.5), vec![(5, 15.0)]);
+
+        // Lists of different lengths snap halfway
+        let uneven = Tween::new(vec![1, 2], vec![3]);
+        assert_eq!(uneven.at(0.4), vec![1, 2]);
+        assert_eq!(uneven.at(0.6), vec![3]);
+
+        let color = Color::red().interpolate(&Color::blue(), 1.5);
+        assert_eq!((color.r, color.g, color.b), (0, 0, 255));
+    }
+
+    // Test in-between shapes of a tween against their golden image
+    #[test]
+    fn test_tween_golden() {
+        let small = Circle::from_center(&Point::new(30, 40), 10)
+            .with_style(Style::new().stroke(Color::rgb(255, 80, 80)).width(1));
+        let large = Circle::from_center(&Point::new(170, 40), 30)
+            .with_style(Style::new().stroke(Color::rgb(80, 160, 255)).width(5));
+        let circles = Tween::new(small, large).with_easing(Easing::CubicInOut);
+
+        let narrow = Rectangle::from_corners(&Point::new(10, 80), &Point::new(20, 110));
+        let wide = Rectangle::from_corners(&Point::new(140, 90), &Point::new(190, 100));
+        let rectangles = Tween::new(narrow, wide).with_easing(Easing::BounceOut);
+
+        let mut image = raster::Image::blank(200, 120);
+        for circle in circles.steps(5) {
+            circle.draw(&mut image);
+        }
+        for rectangle in rectangles.steps(4) {
+            rectangle.draw(&mut image);
+        }
+        golden::assert_golden("tween", &image, Tolerance::EXACT);
+    }
+}
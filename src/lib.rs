@@ -0,0 +1,6 @@
+//! Drawing library
+//!
+//! Only the image encoders live here, so they build without the `raster`
+//! feature; the shapes and the program are in the `drawing` binary.
+
+pub mod encode;
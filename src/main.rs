@@ -1,37 +1,107 @@
+mod animation;
+mod diff;
 mod geometrical_shapes;
+mod terminal;
 
+use drawing::encode;
 use geometrical_shapes as gs;
-use gs::{Displayable, Drawable};
+use gs::{ClipRect, Displayable, Drawable, Styled};
 use raster::{Color, Image};
 
 fn main() {
-    let mut image = Image::blank(1000, 1000);
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().is_some_and(|command| command == "diff") {
+        match diff::run(&args[1..]) {
+            Ok(true) => return,
+            Ok(false) => std::process::exit(1),
+            Err(message) => {
+                eprintln!("{message}");
+                std::process::exit(2);
+            }
+        }
+    }
+
+    if args.first().is_some_and(|command| command == "animate") {
+        if let Err(message) = animation::run(&args[1..]) {
+            eprintln!("{message}");
+            std::process::exit(2);
+        }
+        return;
+    }
+
+    // `--preview` or `--preview=braille` also prints the drawing to the terminal
+    let preview = match args.iter().find(|arg| arg.starts_with("--preview")) {
+        None => None,
+        Some(flag) => match flag.as_str() {
+            "--preview" => Some(terminal::Glyphs::HalfBlock),
+            "--preview=braille" => Some(terminal::Glyphs::Braille),
+            _ => {
+                eprintln!("unknown option {flag}, expected --preview or --preview=braille");
+                std::process::exit(2);
+            }
+        },
+    };
 
-    gs::Line::random(image.width, image.height).draw(&mut image);
+    let (width, height) = (1000, 1000);
 
-    gs::Point::random(image.width, image.height).draw(&mut image);
+    // One palette for the whole scene keeps the colors working together
+    let palette = gs::Palette::random(8);
+    let mut scene: Vec<Box<dyn Drawable + Sync>> = Vec::new();
 
-    let rectangle = gs::Rectangle::new(&gs::Point::new(150, 150), &gs::Point::new(50, 50));
-    rectangle.draw(&mut image);
+    scene.push(Box::new(
+        gs::Line::random(width, height).with_stroke(palette.color(0)),
+    ));
 
-    let triangle = gs::Triangle::new (
-            &gs::Point::new(500, 500),
-            &gs::Point::new(250, 700),
-            &gs::Point::new(700, 800),
-    );
-    triangle.draw(&mut image);
+    scene.push(Box::new(gs::Point::random(width, height)));
 
-    for _ in 1..50 {
-        gs::Circle::random(image.width, image.height).draw(&mut image);
+    let rectangle = gs::Rectangle::new(&gs::Point::new(150, 150), &gs::Point::new(50, 50))
+        .with_stroke(palette.color(1));
+    scene.push(Box::new(rectangle));
+
+    let triangle = gs::Triangle::new(
+        &gs::Point::new(500, 500),
+        &gs::Point::new(250, 700),
+        &gs::Point::new(700, 800),
+    )
+    .with_stroke(palette.color(2));
+    scene.push(Box::new(triangle));
+
+    for i in 1..50 {
+        scene.push(Box::new(
+            gs::Circle::random(width, height).with_stroke(palette.color(i)),
+        ));
     }
 
-    let cube = gs::Cubes::new(&gs::Point::new(650, 250), 150);
-    cube.draw(&mut image);
+    let cube = gs::Cubes::new(&gs::Point::new(650, 250), 150).with_stroke(palette.color(3));
+    scene.push(Box::new(cube));
+
+    let pentagon = gs::Pentagon::new(&gs::Point::new(820, 800), 120).with_stroke(palette.color(4));
+    scene.push(Box::new(pentagon));
 
-    let pentagon = gs::Pentagon::new(&gs::Point::new(820, 800), 120);
-    pentagon.draw(&mut image);
+    let shapes: Vec<&(dyn Drawable + Sync)> = scene.iter().map(|shape| shape.as_ref()).collect();
+    let mut canvas = gs::Canvas::new(width, height);
+    gs::render_tiled(&mut canvas, &shapes, gs::TILE_SIZE);
+
+    if let Some(glyphs) = preview {
+        let preview = terminal::Terminal::from_image(&canvas.to_image());
+        print!("{}", preview.render(terminal::columns(), glyphs));
+    }
 
-    raster::save(&image, "image.png").unwrap();
+    // `--output=<path>` picks the file, .ppm, .pgm, .pam and .bmp are written natively
+    let output = args
+        .iter()
+        .find_map(|arg| arg.strip_prefix("--output="))
+        .unwrap_or("image.png");
+    let saved = match encode::Format::from_path(output) {
+        Some(_) => encode::Pixels::new(width as u32, height as u32, canvas.as_bytes())
+            .and_then(|pixels| pixels.save(output)),
+        None => raster::save(&canvas.to_image(), output)
+            .map_err(|e| encode::EncodeError::Io(format!("{e:?}"))),
+    };
+    if let Err(error) = saved {
+        eprintln!("{error}");
+        std::process::exit(2);
+    }
 }
 
 impl Displayable for Image {
@@ -40,4 +110,124 @@ impl Displayable for Image {
             self.set_pixel(x, y, color).unwrap();
         }
     }
+
+    /// Writes the clipped run straight into the pixel bytes
+    fn display_span(&mut self, left: i32, right: i32, y: i32, color: Color) {
+        if y < 0 || y >= self.height {
+            return;
+        }
+        let (left, right) = (left.max(0), right.min(self.width - 1));
+        if left <= right {
+            let start = (y * self.width + left) as usize * 4;
+            let end = (y * self.width + right + 1) as usize * 4;
+            for pixel in self.bytes[start..end].chunks_exact_mut(4) {
+                pixel.copy_from_slice(&[color.r, color.g, color.b, color.a]);
+            }
+        }
+    }
+
+    /// Lets shapes skip work that would land entirely off the image
+    fn bounds(&self) -> Option<ClipRect> {
+        Some(ClipRect::from_size(self.width, self.height))
+    }
+}
+
+// Unit tests for the Image surface
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    /// Forwards to an image without reporting its bounds,
+    /// forcing shapes down the slow path of discarding every pixel
+    struct Unbounded<'a>(&'a mut Image);
+
+    impl Displayable for Unbounded<'_> {
+        fn display(&mut self, x: i32, y: i32, color: Color) {
+            self.0.display(x, y, color);
+        }
+    }
+
+    /// Builds a scene of huge and mostly off-canvas shapes
+    fn huge_scene(width: i32, height: i32) -> Vec<Box<dyn Drawable>> {
+        let mut scene: Vec<Box<dyn Drawable>> = Vec::new();
+        for i in 0..100 {
+            let offset = 1000 * (i + 1);
+            let far = gs::Point::new(width + offset, height + offset);
+            scene.push(Box::new(gs::Circle::new(&far, offset / 2)));
+            scene.push(Box::new(gs::Circle::new(
+                &gs::Point::new(width / 2, height / 2),
+                offset,
+            )));
+            scene.push(Box::new(gs::Line::new(
+                &gs::Point::new(-offset * 10, height / 2 + i),
+                &gs::Point::new(offset * 10, height / 2 + i),
+            )));
+            scene.push(Box::new(gs::Pentagon::new(&far, offset)));
+        }
+        // A circle far larger than the scene, crossing it along its top
+        let radius = 100_000;
+        scene.push(Box::new(gs::Circle::new(
+            &gs::Point::new(width / 2, height / 2 + radius),
+            radius,
+        )));
+        scene
+    }
+
+    fn pixels(image: &Image) -> Vec<(u8, u8, u8)> {
+        let mut pixels = Vec::new();
+        for y in 0..image.height {
+            for x in 0..image.width {
+                let pixel = image.get_pixel(x, y).unwrap();
+                pixels.push((pixel.r, pixel.g, pixel.b));
+            }
+        }
+        pixels
+    }
+
+    // Test images report their full area as bounds
+    #[test]
+    fn test_image_bounds() {
+        let image = Image::blank(30, 20);
+        let bounds = image.bounds().unwrap();
+
+        assert_eq!((bounds.left, bounds.top), (0, 0));
+        assert_eq!((bounds.right, bounds.bottom), (29, 19));
+        assert!(!image.can_display(&ClipRect::new(30, 0, 40, 10)));
+    }
+
+    // Benchmark early rejection against drawing blind on a huge scene
+    // Run with `make bench`
+    #[test]
+    #[ignore]
+    fn bench_huge_scene() {
+        let scene = huge_scene(500, 500);
+
+        let mut bounded = Image::blank(500, 500);
+        let start = Instant::now();
+        for shape in &scene {
+            shape.draw(&mut bounded);
+        }
+        let fast = start.elapsed();
+
+        let mut unbounded = Image::blank(500, 500);
+        let start = Instant::now();
+        for shape in &scene {
+            shape.draw(&mut Unbounded(&mut unbounded));
+        }
+        let slow = start.elapsed();
+
+        println!("with early rejection:    {fast:?}");
+        println!("without early rejection: {slow:?}");
+        assert_eq!(pixels(&bounded), pixels(&unbounded));
+
+        // Only the part of the outline crossing the image is walked
+        let radius = 20_000_000;
+        let circle = gs::Circle::new(&gs::Point::new(250, 250 + radius), radius);
+        let mut image = Image::blank(500, 500);
+        let start = Instant::now();
+        circle.draw(&mut image);
+        println!("circle of radius {radius}: {:?}", start.elapsed());
+        assert_eq!(image.get_pixel(250, 250).unwrap().r, 255);
+    }
 }
@@ -0,0 +1,310 @@
+//! Terminal preview module
+//!
+//! A drawing surface that prints itself with 24-bit ANSI colors, using
+//! half-block or braille characters, for quick previews over SSH with
+//! `cargo run -- --preview`.
+
+use crate::geometrical_shapes::{ClipRect, Displayable};
+use raster::{Color, Image};
+use std::fmt::Write;
+
+/// Characters a preview is drawn with
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Glyphs {
+    /// `▀` with separate colors above and below, two square pixels per character
+    #[default]
+    HalfBlock,
+    /// Braille dots, 2x4 pixels per character in one color, for finer lines
+    Braille,
+}
+
+/// A surface kept in memory and printed to the terminal, scaled down to fit
+#[derive(Debug, Clone)]
+pub struct Terminal {
+    width: i32,
+    height: i32,
+    pixels: Vec<[u8; 3]>,
+    background: [u8; 3],
+}
+
+/// Sets the foreground or background color, `layer` 38 or 48
+fn ansi(out: &mut String, layer: u8, [r, g, b]: [u8; 3]) {
+    let _ = write!(out, "\x1b[{layer};2;{r};{g};{b}m");
+}
+
+/// Rounded mean of the summed channels
+fn mean(sum: [u32; 3], count: u32) -> [u8; 3] {
+    sum.map(|channel| ((channel + count / 2) / count) as u8)
+}
+
+#[allow(dead_code)]
+impl Terminal {
+    /// Creates a black surface of the given size in pixels
+    pub fn new(width: i32, height: i32) -> Self {
+        let (width, height) = (width.max(0), height.max(0));
+        Terminal {
+            width,
+            height,
+            pixels: vec![[0; 3]; width as usize * height as usize],
+            background: [0; 3],
+        }
+    }
+
+    /// Copies an already drawn image, treating its top left pixel as the background
+    pub fn from_image(image: &Image) -> Self {
+        let mut terminal = Terminal::new(image.width, image.height);
+        for (pixel, bytes) in terminal.pixels.iter_mut().zip(image.bytes.chunks_exact(4)) {
+            *pixel = [bytes[0], bytes[1], bytes[2]];
+        }
+        if let Some(first) = terminal.pixels.first() {
+            terminal.background = *first;
+        }
+        terminal
+    }
+
+    /// Color of a pixel, `None` outside the surface
+    pub fn get_pixel(&self, x: i32, y: i32) -> Option<Color> {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return None;
+        }
+        let [r, g, b] = self.pixels[(y * self.width + x) as usize];
+        Some(Color::rgb(r, g, b))
+    }
+
+    /// Shrinks the surface to `columns` by however many rows keep its proportions
+    /// Each cell shows the mean color of the pixels it covers that differ from the
+    /// background, so lines a single pixel wide survive the downsampling
+    fn downsample(&self, columns: usize) -> (usize, usize, Vec<Option<[u8; 3]>>) {
+        let columns = columns.clamp(1, self.width.max(1) as usize);
+        let scale = self.width as f64 / columns as f64;
+        let rows = ((self.height as f64 / scale).ceil() as usize).max(1);
+
+        let mut cells = vec![None; columns * rows];
+        for (row, line) in cells.chunks_mut(columns).enumerate() {
+            let top = (row as f64 * scale) as i32;
+            let bottom = (((row + 1) as f64 * scale) as i32).clamp(top + 1, self.height.max(1));
+            for (column, cell) in line.iter_mut().enumerate() {
+                let left = (column as f64 * scale) as i32;
+                let right = (((column + 1) as f64 * scale) as i32).max(left + 1);
+
+                let (mut sum, mut count) = ([0u32; 3], 0);
+                for y in top..bottom.min(self.height) {
+                    for x in left..right.min(self.width) {
+                        let pixel = self.pixels[(y * self.width + x) as usize];
+                        if pixel != self.background {
+                            for c in 0..3 {
+                                sum[c] += pixel[c] as u32;
+                            }
+                            count += 1;
+                        }
+                    }
+                }
+                *cell = (count > 0).then(|| mean(sum, count));
+            }
+        }
+        (columns, rows, cells)
+    }
+
+    /// Renders the surface `columns` characters wide as ANSI text, one line per row
+    /// Every line ends by resetting the colors
+    pub fn render(&self, columns: usize, glyphs: Glyphs) -> String {
+        let mut out = String::new();
+        if self.width == 0 || self.height == 0 {
+            return out;
+        }
+
+        match glyphs {
+            Glyphs::HalfBlock => {
+                let (columns, rows, cells) = self.downsample(columns);
+                let color = |column: usize, row: usize| {
+                    (row < rows)
+                        .then(|| cells[row * columns + column])
+                        .flatten()
+                        .unwrap_or(self.background)
+                };
+
+                for row in (0..rows).step_by(2) {
+                    let mut current = None;
+                    for column in 0..columns {
+                        let pair = (color(column, row), color(column, row + 1));
+                        if current != Some(pair) {
+                            ansi(&mut out, 38, pair.0);
+                            ansi(&mut out, 48, pair.1);
+                            current = Some(pair);
+                        }
+                        out.push('▀');
+                    }
+                    out.push_str("\x1b[0m\n");
+                }
+            }
+            Glyphs::Braille => {
+                let (dots_x, dots_y, cells) = self.downsample(columns * 2);
+                for row in (0..dots_y).step_by(4) {
+                    let mut current = None;
+                    for column in (0..dots_x).step_by(2) {
+                        let (mut bits, mut sum, mut count) = (0u32, [0u32; 3], 0);
+                        for (dx, dy, bit) in [
+                            (0, 0, 0x01),
+                            (0, 1, 0x02),
+                            (0, 2, 0x04),
+                            (1, 0, 0x08),
+                            (1, 1, 0x10),
+                            (1, 2, 0x20),
+                            (0, 3, 0x40),
+                            (1, 3, 0x80),
+                        ] {
+                            let (x, y) = (column + dx, row + dy);
+                            if let Some(dot) = (x < dots_x && y < dots_y)
+                                .then(|| cells[y * dots_x + x])
+                                .flatten()
+                            {
+                                bits |= bit;
+                                for c in 0..3 {
+                                    sum[c] += dot[c] as u32;
+                                }
+                                count += 1;
+                            }
+                        }
+
+                        if count == 0 {
+                            out.push(' ');
+                            continue;
+                        }
+                        let color = mean(sum, count);
+                        if current != Some(color) {
+                            ansi(&mut out, 38, color);
+                            current = Some(color);
+                        }
+                        out.push(char::from_u32(0x2800 + bits).unwrap_or(' '));
+                    }
+                    out.push_str("\x1b[0m\n");
+                }
+            }
+        }
+        out
+    }
+}
+
+impl Displayable for Terminal {
+    fn display(&mut self, x: i32, y: i32, color: Color) {
+        if x >= 0 && x < self.width && y >= 0 && y < self.height {
+            self.pixels[(y * self.width + x) as usize] = [color.r, color.g, color.b];
+        }
+    }
+
+    fn bounds(&self) -> Option<ClipRect> {
+        Some(ClipRect::from_size(self.width, self.height))
+    }
+}
+
+/// Width of the terminal in characters, from `COLUMNS` when set, 80 otherwise
+pub fn columns() -> usize {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.trim().parse().ok())
+        .filter(|&columns| columns > 0)
+        .unwrap_or(80)
+}
+
+// Unit tests for the terminal preview
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometrical_shapes::{Drawable, Line, Point};
+
+    /// Text of a preview with the escape sequences removed
+    fn glyphs(text: &str) -> String {
+        let mut plain = String::new();
+        let mut escape = false;
+        for c in text.chars() {
+            match c {
+                '\x1b' => escape = true,
+                'm' if escape => escape = false,
+                _ if !escape => plain.push(c),
+                _ => {}
+            }
+        }
+        plain
+    }
+
+    // Test the surface stores pixels like an image and ignores the outside
+    #[test]
+    fn test_terminal_display() {
+        let mut terminal = Terminal::new(10, 6);
+        terminal.display(3, 2, Color::rgb(1, 2, 3));
+        terminal.display(-1, 2, Color::white());
+        terminal.display(10, 0, Color::white());
+
+        let pixel = terminal.get_pixel(3, 2).unwrap();
+        assert_eq!((pixel.r, pixel.g, pixel.b), (1, 2, 3));
+        assert!(terminal.get_pixel(10, 0).is_none());
+        assert!(!terminal.can_display(&ClipRect::new(20, 0, 30, 5)));
+    }
+
+    // Test half blocks pair two pixel rows per line in true color
+    #[test]
+    fn test_terminal_half_block() {
+        let mut terminal = Terminal::new(4, 4);
+        terminal.display(0, 0, Color::red());
+        terminal.display(0, 1, Color::blue());
+
+        let text = terminal.render(4, Glyphs::HalfBlock);
+        assert_eq!(glyphs(&text), "▀▀▀▀\n▀▀▀▀\n");
+        assert!(text.starts_with("\x1b[38;2;255;0;0m\x1b[48;2;0;0;255m▀"));
+        assert!(text.lines().all(|line| line.ends_with("\x1b[0m")));
+
+        // Colors are only repeated when they change
+        assert_eq!(text.matches("\x1b[38;2;0;0;0m").count(), 2);
+    }
+
+    // Test braille dots follow the standard bit layout
+    #[test]
+    fn test_terminal_braille() {
+        let mut terminal = Terminal::new(4, 4);
+        for (x, y) in [(0, 0), (1, 3), (3, 1)] {
+            terminal.display(x, y, Color::green());
+        }
+
+        let text = terminal.render(2, Glyphs::Braille);
+        assert_eq!(glyphs(&text), "\u{2881}\u{2810}\n");
+        assert!(text.contains("\x1b[38;2;0;255;0m"));
+        assert_eq!(
+            glyphs(&Terminal::new(4, 4).render(2, Glyphs::Braille)),
+            "  \n"
+        );
+    }
+
+    // Test downsampling keeps thin lines and the proportions of the surface
+    #[test]
+    fn test_terminal_downsample() {
+        let mut terminal = Terminal::new(100, 50);
+        Line::from_points(&Point::new(0, 25), &Point::new(99, 25), 1, Color::white())
+            .draw(&mut terminal);
+
+        let (columns, rows, cells) = terminal.downsample(20);
+        assert_eq!((columns, rows), (20, 10));
+        assert!((0..20).all(|x| cells[5 * 20 + x] == Some([255; 3])));
+        assert_eq!(cells.iter().flatten().count(), 20);
+
+        let text = terminal.render(20, Glyphs::HalfBlock);
+        assert_eq!(text.lines().count(), 5);
+        assert_eq!(glyphs(&text).lines().next().unwrap().chars().count(), 20);
+
+        // Never wider than the surface itself
+        assert_eq!(terminal.downsample(500).0, 100);
+        assert!(Terminal::new(0, 0).render(80, Glyphs::Braille).is_empty());
+    }
+
+    // Test images keep their pixels and background when copied
+    #[test]
+    fn test_terminal_from_image() {
+        let mut image = Image::blank(8, 8);
+        image.set_pixel(0, 0, Color::rgb(9, 9, 9)).unwrap();
+        image.set_pixel(5, 5, Color::white()).unwrap();
+
+        let terminal = Terminal::from_image(&image);
+        assert_eq!(terminal.background, [9, 9, 9]);
+        assert_eq!(terminal.get_pixel(5, 5).unwrap().r, 255);
+    }
+}